@@ -1,9 +1,9 @@
 use std::sync::Arc;
 
 use arrow::array::Array as ArrowArray;
-use arrow::datatypes::Field as ArrowField;
+use arrow::datatypes::{DataType as ArrowDataType, Field as ArrowField};
 
-use tiledb_cxx_interface::sm::array_schema::Enumeration;
+use tiledb_cxx_interface::sm::array_schema::{CellValNum, Datatype, Enumeration, new_enumeration};
 
 use crate::{record_batch, schema};
 
@@ -13,6 +13,13 @@ pub enum Error {
     DataType(#[from] crate::schema::FieldError),
     #[error("Enumeration variants error: {0}")]
     Variants(#[from] crate::record_batch::FieldError),
+    #[error("Array has type {found}, expected {expected} for this enumeration's declared type")]
+    UnexpectedArrowDataType {
+        expected: ArrowDataType,
+        found: ArrowDataType,
+    },
+    #[error("Enumeration offsets are not aligned to u64")]
+    UnalignedOffsets,
 }
 
 /// Returns an [ArrowArray] whose elements are the variants of an [Enumeration].
@@ -55,9 +62,55 @@ pub unsafe fn array_from_enumeration(
             offsets.align_to::<u8>()
         };
         Ok(unsafe {
-            record_batch::to_arrow_array(&field, offsets, Some(enumeration.data()), None)
+            record_batch::to_arrow_array(&field, offsets, Some(enumeration.data()), None, None)
         }?)
     } else {
-        Ok(unsafe { record_batch::to_arrow_array(&field, enumeration.data(), None, None) }?)
+        Ok(unsafe { record_batch::to_arrow_array(&field, enumeration.data(), None, None, None) }?)
     }
 }
+
+/// Materializes a new TileDB [Enumeration] named `name` whose variants are
+/// the elements of `array`. `array`'s type must be what [schema::arrow_datatype]
+/// returns for `datatype`/`cell_val_num`, mirroring the `offsets()`/`data()`
+/// split of [array_from_enumeration] in reverse.
+pub fn enumeration_from_array(
+    name: &str,
+    array: &dyn ArrowArray,
+    datatype: Datatype,
+    cell_val_num: CellValNum,
+) -> Result<cxx::SharedPtr<Enumeration>, Error> {
+    let expected = schema::arrow_datatype(datatype, cell_val_num)?;
+    if array.data_type() != &expected {
+        return Err(Error::UnexpectedArrowDataType {
+            expected,
+            found: array.data_type().clone(),
+        });
+    }
+
+    let field = ArrowField::new(name, expected, false);
+    let record_batch::TileBuffers { fixed, var, .. } = record_batch::from_arrow_array(&field, array)?;
+
+    let (data, offsets) = match var {
+        Some(data) => {
+            let (prefix, offsets, suffix) = unsafe {
+                // SAFETY: transmuting u8 to u64 always succeeds; alignment
+                // is checked below
+                fixed.align_to::<u64>()
+            };
+            if !prefix.is_empty() || !suffix.is_empty() {
+                return Err(Error::UnalignedOffsets);
+            }
+            (data, offsets.to_vec())
+        }
+        None => (fixed, Vec::new()),
+    };
+
+    cxx::let_cxx_string!(cxxname = name);
+    Ok(new_enumeration(
+        &cxxname,
+        datatype,
+        cell_val_num.into(),
+        &data,
+        &offsets,
+    ))
+}
@@ -3,34 +3,43 @@
 //! The functions in this module are `unsafe` because the FFI boundary
 //! prevents us from expressing a lifetime relationship between the
 //! returned [RecordBatch] and the argument [ResultTile].
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use arrow::array::transform::{Capacities, MutableArrayData};
 use arrow::array::{
-    self as aa, Array as ArrowArray, FixedSizeListArray, GenericListArray, LargeStringArray,
-    PrimitiveArray,
+    self as aa, Array as ArrowArray, ArrayData, BinaryViewArray, DictionaryArray,
+    FixedSizeListArray, GenericListArray, LargeStringArray, PrimitiveArray, StringViewArray,
 };
 use arrow::buffer::{Buffer, NullBuffer, OffsetBuffer, ScalarBuffer};
-use arrow::datatypes::{self as adt, ArrowPrimitiveType, Field};
+use arrow::datatypes::{self as adt, ArrowPrimitiveType, DataType, Field};
 use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use tiledb_cxx_interface::sm::array_schema::ArraySchema;
 use tiledb_cxx_interface::sm::query::readers::{ResultTile, TileTuple};
 
 use super::*;
 use crate::offsets::Error as OffsetsError;
 
-/// An error creating a [RecordBatch] to represent a [ResultTile].
+/// An error creating a [RecordBatch] to represent a [ResultTile], or an
+/// error going the other way via [from_record_batch].
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("Cannot process field '{0}': {1}")]
     FieldError(String, #[source] FieldError),
+    #[error("Unknown dimension or attribute: {0}")]
+    UnknownField(String),
 }
 
-/// An error creating an [ArrowArray] for a specific field of a tile.
+/// An error creating an [ArrowArray] for a specific field of a tile, or an
+/// error going the other way via [from_arrow_array].
 #[derive(Debug, thiserror::Error)]
 pub enum FieldError {
     #[error("Internal error: invalid data type: {0}")]
     ArrowDataType(#[from] crate::schema::FieldError),
     #[error("Unexpected validity tile for non-nullable field")]
     UnexpectedValidityTile,
+    #[error("Expected validity tile for nullable field")]
+    ExpectedValidityTile,
     #[error("Unexpected offsets tile for fixed-length field")]
     UnexpectedVarTile,
     #[error("Expected offsets tile for field with variable cell val num")]
@@ -43,6 +52,10 @@ pub enum FieldError {
     InvalidTileData(#[source] arrow::error::ArrowError),
     #[error("Attributes with enumerations are not supported in text predicates")]
     EnumerationNotSupported,
+    #[error("Field has a dictionary type but no enumeration variants were loaded for it")]
+    MissingEnumerationValues,
+    #[error("Unsupported arrow data type: {0}")]
+    UnsupportedArrowDataType(adt::DataType),
 }
 
 /// Wraps a [RecordBatch] for passing across the FFI boundary.
@@ -81,10 +94,15 @@ pub unsafe fn to_record_batch(
                 // SAFETY: diverging `is_null` above
                 &*ptr_tile
             };
+            let dictionary_values = f
+                .metadata()
+                .get("enumeration")
+                .and_then(|ename| schema.enumerations.get(ename))
+                .and_then(|variants| variants.as_ref());
             unsafe {
                 // SAFETY: the caller is responsible that each attribute tile in `tile`
                 // out-lives the `Arc<dyn ArrowArray>` created here. See function docs.
-                tile_to_arrow_array(f, tile)
+                tile_to_arrow_array(f, tile, dictionary_values)
             }
             .map_err(|e| Error::FieldError(f.name().to_owned(), e))
         })
@@ -131,6 +149,208 @@ pub unsafe fn to_record_batch(
     Ok(Box::new(ArrowRecordBatch { arrow }))
 }
 
+/// Safely pairs a [RecordBatch] view of a [ResultTile] with the tile it
+/// borrows from, so that the zero-copied buffers [to_record_batch] returns
+/// cannot outlive the tile they alias: rather than handing that `unsafe fn`
+/// back to the caller to pair up correctly on its own, this holds the
+/// [cxx::SharedPtr] as a field alongside the batch, so the two are always
+/// dropped together.
+pub struct ResultTileRecordBatch {
+    batch: RecordBatch,
+    // NB: `batch` may borrow into `result_tile`'s tile buffers; keeping the
+    // `SharedPtr` alive here for as long as `batch` is is what discharges
+    // `to_record_batch`'s safety requirement.
+    #[allow(dead_code)]
+    result_tile: cxx::SharedPtr<ResultTile>,
+}
+
+impl ResultTileRecordBatch {
+    /// Builds the [RecordBatch] view of `result_tile`, keeping `result_tile`
+    /// alive for as long as the returned [ResultTileRecordBatch] is.
+    pub fn try_new(
+        schema: &ArrowArraySchema,
+        result_tile: cxx::SharedPtr<ResultTile>,
+    ) -> Result<Self, Error> {
+        let tile_ref = result_tile
+            .as_ref()
+            .expect("result_tile passed to ResultTileRecordBatch::try_new is null");
+        let batch = unsafe {
+            // SAFETY: `result_tile` is stored in `self` below, so it is not
+            // dropped before `batch`, which is all `to_record_batch` requires.
+            to_record_batch(schema, tile_ref)?
+        };
+        Ok(ResultTileRecordBatch {
+            batch: batch.arrow,
+            result_tile,
+        })
+    }
+
+    pub fn batch(&self) -> &RecordBatch {
+        &self.batch
+    }
+}
+
+/// Returns a single [RecordBatch] containing the concatenated contents of
+/// `tiles`, in order.
+///
+/// Each tile's per-field data is zero-copyable on its own (see
+/// [to_record_batch]), but one tile's buffers cannot be aliased into
+/// another's: each [ResultTile] owns its own distinct C++ buffers, so
+/// combining many of them into one [RecordBatch] always requires a copy.
+/// Appending one tile's arrays at a time would reallocate each output
+/// buffer repeatedly as it grows; instead, this first walks all tiles to
+/// compute every field's total [Capacities] -- recursively including the
+/// total child-element count for nested `FixedSizeList`/`LargeList` fields
+/// -- so [MutableArrayData] can allocate each output buffer exactly once.
+///
+/// # Safety
+///
+/// Every [ResultTile] in `tiles` is fully read before this function
+/// returns, so (unlike [to_record_batch]) the returned [RecordBatch] does
+/// not borrow from any of them and may safely outlive them.
+pub unsafe fn to_record_batch_many(
+    schema: &ArrowArraySchema,
+    tiles: &[&ResultTile],
+) -> Result<Box<ArrowRecordBatch>, Error> {
+    let Some((first, rest)) = tiles.split_first() else {
+        return Ok(Box::new(ArrowRecordBatch {
+            arrow: RecordBatch::new_empty(Arc::clone(&schema.schema)),
+        }));
+    };
+    if rest.is_empty() {
+        return unsafe { to_record_batch(schema, first) };
+    }
+
+    let total_cells = tiles.iter().map(|t| t.cell_num()).sum::<u64>() as usize;
+
+    let columns = schema
+        .schema
+        .fields()
+        .iter()
+        .map(|f| {
+            let per_tile = tiles
+                .iter()
+                .map(|tile| {
+                    let ptr_tile = {
+                        cxx::let_cxx_string!(fname = f.name());
+                        tile.tile_tuple(&fname)
+                    };
+                    if ptr_tile.is_null() {
+                        return Ok(aa::new_null_array(f.data_type(), tile.cell_num() as usize));
+                    }
+
+                    let tile_ref = unsafe {
+                        // SAFETY: diverging `is_null` above
+                        &*ptr_tile
+                    };
+                    let dictionary_values = f
+                        .metadata()
+                        .get("enumeration")
+                        .and_then(|ename| schema.enumerations.get(ename))
+                        .and_then(|variants| variants.as_ref());
+                    unsafe {
+                        // SAFETY: every array built here is read in full (via
+                        // `concat_with_capacity`'s `MutableArrayData::extend`)
+                        // before this function returns, so none of it outlives
+                        // `tile_ref`/`tile`.
+                        tile_to_arrow_array(f, tile_ref, dictionary_values)
+                    }
+                })
+                .collect::<Result<Vec<Arc<dyn ArrowArray>>, _>>()
+                .map_err(|e| Error::FieldError(f.name().to_owned(), e))?;
+
+            Ok(concat_with_capacity(f.data_type(), &per_tile))
+        })
+        .collect::<Result<Vec<Arc<dyn ArrowArray>>, Error>>()?;
+
+    assert!(
+        columns.iter().all(|c| c.len() == total_cells),
+        "Columns do not all have the concatenated number of cells: {:?} {:?}",
+        schema.schema.fields(),
+        columns.iter().map(|c| c.len()).collect::<Vec<_>>()
+    );
+
+    let arrow = RecordBatch::try_new(Arc::clone(&schema.schema), columns)
+        .expect("Logic error: preconditions for constructing RecordBatch not met");
+
+    Ok(Box::new(ArrowRecordBatch { arrow }))
+}
+
+/// Concatenates `arrays` (all of type `data_type`) into a single array,
+/// preallocating its buffers exactly once via [MutableArrayData] rather than
+/// growing them as each source array is appended.
+fn concat_with_capacity(data_type: &DataType, arrays: &[Arc<dyn ArrowArray>]) -> Arc<dyn ArrowArray> {
+    let capacity = capacities(data_type, arrays);
+    let array_data: Vec<ArrayData> = arrays.iter().map(|a| a.to_data()).collect();
+
+    let mut mutable = MutableArrayData::with_capacities(array_data.iter().collect(), false, capacity);
+    for (i, array) in arrays.iter().enumerate() {
+        mutable.extend(i, 0, array.len());
+    }
+
+    aa::make_array(mutable.freeze())
+}
+
+/// Recursively computes the [Capacities] needed to hold the concatenation of
+/// `arrays` without reallocating.
+fn capacities(data_type: &DataType, arrays: &[Arc<dyn ArrowArray>]) -> Capacities {
+    let total_len = arrays.iter().map(|a| a.len()).sum();
+    match data_type {
+        DataType::LargeUtf8 | DataType::LargeBinary => {
+            let total_bytes = arrays
+                .iter()
+                .map(|a| {
+                    if let Some(a) = a.as_any().downcast_ref::<LargeStringArray>() {
+                        a.value_data().len()
+                    } else {
+                        a.as_any()
+                            .downcast_ref::<aa::LargeBinaryArray>()
+                            .expect("array data type matches field data type")
+                            .value_data()
+                            .len()
+                    }
+                })
+                .sum();
+            Capacities::Binary(total_len, Some(total_bytes))
+        }
+        DataType::FixedSizeList(child_field, _) => {
+            let child_arrays: Vec<Arc<dyn ArrowArray>> = arrays
+                .iter()
+                .map(|a| {
+                    Arc::clone(
+                        a.as_any()
+                            .downcast_ref::<FixedSizeListArray>()
+                            .expect("array data type matches field data type")
+                            .values(),
+                    )
+                })
+                .collect();
+            Capacities::List(
+                total_len,
+                Some(Box::new(capacities(child_field.data_type(), &child_arrays))),
+            )
+        }
+        DataType::LargeList(child_field) => {
+            let child_arrays: Vec<Arc<dyn ArrowArray>> = arrays
+                .iter()
+                .map(|a| {
+                    Arc::clone(
+                        a.as_any()
+                            .downcast_ref::<GenericListArray<i64>>()
+                            .expect("array data type matches field data type")
+                            .values(),
+                    )
+                })
+                .collect();
+            Capacities::List(
+                total_len,
+                Some(Box::new(capacities(child_field.data_type(), &child_arrays))),
+            )
+        }
+        _ => Capacities::Array(total_len),
+    }
+}
+
 /// Returns an [ArrowArray] which contains the same contents as the provided
 /// [TileTuple].
 ///
@@ -143,6 +363,7 @@ pub unsafe fn to_record_batch(
 unsafe fn tile_to_arrow_array(
     f: &Field,
     tile: &TileTuple,
+    dictionary_values: Option<&Arc<dyn ArrowArray>>,
 ) -> Result<Arc<dyn ArrowArray>, FieldError> {
     unsafe {
         // SAFETY: the caller is responsible that each of the tiles tile out-live
@@ -152,6 +373,7 @@ unsafe fn tile_to_arrow_array(
             tile.fixed_tile().as_slice(),
             tile.var_tile().map(|t| t.as_slice()),
             tile.validity_tile().map(|t| t.as_slice()),
+            dictionary_values,
         )
     }
 }
@@ -164,6 +386,10 @@ unsafe fn tile_to_arrow_array(
 ///
 /// The `validity` `&[u8]` contains one value per cell.
 ///
+/// `dictionary_values` supplies the dictionary values for a field whose
+/// [Field::data_type] is [DataType::Dictionary] (i.e. an enumerated
+/// attribute); it is ignored otherwise. See the `Dictionary` match arm below.
+///
 /// # Safety
 ///
 /// When possible this function avoids copying data. This means that the
@@ -175,6 +401,7 @@ pub unsafe fn to_arrow_array(
     fixed: &[u8],
     var: Option<&[u8]>,
     validity: Option<&[u8]>,
+    dictionary_values: Option<&Arc<dyn ArrowArray>>,
 ) -> Result<Arc<dyn ArrowArray>, FieldError> {
     let null_buffer = if let Some(validity) = validity {
         if !f.is_nullable() {
@@ -219,7 +446,7 @@ pub unsafe fn to_arrow_array(
             let values = unsafe {
                 // SAFETY: the caller is responsible that the `fixed` tile out-lives
                 // the `PrimitiveArray` created here. See function docs.
-                to_arrow_array(value_field, fixed, None, None)?
+                to_arrow_array(value_field, fixed, None, None, None)?
             };
             Ok(Arc::new(FixedSizeListArray::new(
                 Arc::clone(value_field),
@@ -244,6 +471,40 @@ pub unsafe fn to_arrow_array(
                     .map_err(FieldError::InvalidTileData)?,
             ))
         }
+        dt @ (DataType::Utf8View | DataType::BinaryView) => {
+            let Some(var_tile) = var else {
+                return Err(FieldError::ExpectedVarTile);
+            };
+            let offsets = crate::offsets::try_from_bytes(1, fixed)?;
+            let values_buffer: Buffer = unsafe {
+                // SAFETY: the caller is responsible that `var_tile` out-lives
+                // the `Buffer` created here. See function docs.
+                to_buffer::<UInt8Type>(var_tile)
+            }?
+            .into_inner();
+
+            let views: Vec<u128> = offsets
+                .windows(2)
+                .map(|w| {
+                    let start = w[0] as usize;
+                    let end = w[1] as usize;
+                    byte_view(&var_tile[start..end], start as u32)
+                })
+                .collect();
+            let views = ScalarBuffer::from(views);
+
+            if matches!(dt, DataType::Utf8View) {
+                Ok(Arc::new(
+                    StringViewArray::try_new(views, vec![values_buffer], null_buffer)
+                        .map_err(FieldError::InvalidTileData)?,
+                ))
+            } else {
+                Ok(Arc::new(
+                    BinaryViewArray::try_new(views, vec![values_buffer], null_buffer)
+                        .map_err(FieldError::InvalidTileData)?,
+                ))
+            }
+        }
         DataType::LargeList(value_field) => {
             let Some(var_tile) = var else {
                 return Err(FieldError::ExpectedVarTile);
@@ -252,7 +513,7 @@ pub unsafe fn to_arrow_array(
             let values = unsafe {
                 // SAFETY: the caller is responsible that `var_tile` out-lives
                 // the `PrimitiveArray` created here. See function docs.
-                to_arrow_array(value_field, var_tile, None, None)?
+                to_arrow_array(value_field, var_tile, None, None, None)?
             };
             Ok(Arc::new(GenericListArray::new(
                 Arc::clone(value_field),
@@ -261,10 +522,50 @@ pub unsafe fn to_arrow_array(
                 null_buffer,
             )))
         }
+        DataType::Dictionary(key_type, _) => {
+            if var.is_some() {
+                return Err(FieldError::UnexpectedVarTile);
+            }
+            let Some(values) = dictionary_values else {
+                return Err(FieldError::MissingEnumerationValues);
+            };
+
+            macro_rules! match_arm_dictionary {
+                ($keytype:ty) => {{
+                    let keys = unsafe {
+                        // SAFETY: the caller is responsible that the `fixed` tile
+                        // out-lives the `PrimitiveArray` created here. See function docs.
+                        to_buffer::<$keytype>(fixed)
+                    }?;
+                    let keys = PrimitiveArray::<$keytype>::new(keys, null_buffer);
+                    Ok(Arc::new(
+                        DictionaryArray::<$keytype>::try_new(keys, Arc::clone(values))
+                            .map_err(FieldError::InvalidTileData)?,
+                    ))
+                }};
+            }
+
+            match key_type.as_ref() {
+                DataType::Int8 => match_arm_dictionary!(adt::Int8Type),
+                DataType::Int16 => match_arm_dictionary!(adt::Int16Type),
+                DataType::Int32 => match_arm_dictionary!(adt::Int32Type),
+                DataType::Int64 => match_arm_dictionary!(adt::Int64Type),
+                DataType::UInt8 => match_arm_dictionary!(adt::UInt8Type),
+                DataType::UInt16 => match_arm_dictionary!(adt::UInt16Type),
+                DataType::UInt32 => match_arm_dictionary!(adt::UInt32Type),
+                DataType::UInt64 => match_arm_dictionary!(adt::UInt64Type),
+                other => unreachable!(
+                    "Dictionary key has unexpected data type for arrow array: {:?}",
+                    other
+                ),
+            }
+        }
         DataType::Null => {
             // NB: see `arrow/src/schema.rs`.
             // This represents the value type of an attribute with an enumeration
-            // which we will implement later in CORE-285.
+            // whose variants have not been loaded (see `field_arrow_datatype`'s
+            // `WhichSchema::View` branch); we have no storage key type to pair
+            // with a value type in that case, so there is nothing to build here.
             Err(FieldError::EnumerationNotSupported)
         }
         _ => {
@@ -277,6 +578,180 @@ pub unsafe fn to_arrow_array(
     }
 }
 
+/// The fixed/var/validity byte buffers TileDB expects for one field's tile;
+/// the inverse of the triple [to_arrow_array] reads from.
+pub struct TileBuffers {
+    /// Fixed-size data, or (if `var` is `Some`) the byte offsets into `var`.
+    pub fixed: Vec<u8>,
+    /// Variable-length value data, present for fields with a variable
+    /// cell val num.
+    pub var: Option<Vec<u8>>,
+    /// One byte per cell, nonzero meaning valid; present for nullable fields.
+    pub validity: Option<Vec<u8>>,
+}
+
+/// Returns the per-field [TileBuffers] needed to write `batch` into an array
+/// with `array_schema`.
+///
+/// `batch`'s schema is expected to be the "array storage" schema of
+/// `array_schema` (see `crate::schema`'s module docs), e.g. as built by
+/// [crate::schema::to_arrow] with `WhichSchema::Storage`.
+pub fn from_record_batch(
+    array_schema: &ArraySchema,
+    batch: &RecordBatch,
+) -> Result<HashMap<String, TileBuffers>, Error> {
+    batch
+        .schema()
+        .fields()
+        .iter()
+        .zip(batch.columns())
+        .map(|(f, array)| {
+            if array_schema.field(f.name()).is_none() {
+                return Err(Error::UnknownField(f.name().to_owned()));
+            }
+            let buffers = from_arrow_array(f, array.as_ref())
+                .map_err(|e| Error::FieldError(f.name().to_owned(), e))?;
+            Ok((f.name().to_owned(), buffers))
+        })
+        .collect()
+}
+
+/// Returns the [TileBuffers] which contain the same contents as `array`.
+/// This is the inverse of [to_arrow_array].
+pub(crate) fn from_arrow_array(f: &Field, array: &dyn ArrowArray) -> Result<TileBuffers, FieldError> {
+    let validity = if f.is_nullable() {
+        Some(
+            (0..array.len())
+                .map(|i| array.is_valid(i) as u8)
+                .collect::<Vec<u8>>(),
+        )
+    } else if array.null_count() > 0 {
+        return Err(FieldError::UnexpectedValidityTile);
+    } else {
+        None
+    };
+
+    macro_rules! match_arm_primitive {
+        ($primitivetype:ty) => {{
+            let values = array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<$primitivetype>>()
+                .expect("Logic error: array does not match its own data type")
+                .values();
+            TileBuffers {
+                fixed: values.inner().as_slice().to_vec(),
+                var: None,
+                validity,
+            }
+        }};
+    }
+
+    use adt::*;
+    Ok(match array.data_type() {
+        DataType::Int8 => match_arm_primitive!(Int8Type),
+        DataType::Int16 => match_arm_primitive!(Int16Type),
+        DataType::Int32 => match_arm_primitive!(Int32Type),
+        DataType::Int64 => match_arm_primitive!(Int64Type),
+        DataType::UInt8 => match_arm_primitive!(UInt8Type),
+        DataType::UInt16 => match_arm_primitive!(UInt16Type),
+        DataType::UInt32 => match_arm_primitive!(UInt32Type),
+        DataType::UInt64 => match_arm_primitive!(UInt64Type),
+        DataType::Float32 => match_arm_primitive!(Float32Type),
+        DataType::Float64 => match_arm_primitive!(Float64Type),
+        DataType::FixedSizeList(value_field, _) => {
+            let list = array
+                .as_any()
+                .downcast_ref::<FixedSizeListArray>()
+                .expect("Logic error: array does not match its own data type");
+            let values = from_arrow_array(value_field, list.values().as_ref())?;
+            TileBuffers {
+                fixed: values.fixed,
+                var: None,
+                validity,
+            }
+        }
+        DataType::LargeUtf8 => {
+            let strings = array
+                .as_any()
+                .downcast_ref::<LargeStringArray>()
+                .expect("Logic error: array does not match its own data type");
+            TileBuffers {
+                fixed: byte_offsets_from_element_offsets(strings.offsets(), 1),
+                var: Some(strings.value_data().to_vec()),
+                validity,
+            }
+        }
+        DataType::LargeList(value_field) => {
+            let list = array
+                .as_any()
+                .downcast_ref::<GenericListArray<i64>>()
+                .expect("Logic error: array does not match its own data type");
+            let Some(value_size) = value_field.data_type().primitive_width() else {
+                // SAFETY: all list types we produce have primitive elements
+                unreachable!(
+                    "Unexpected list field element type: {}",
+                    value_field.data_type()
+                )
+            };
+            let values = from_arrow_array(value_field, list.values().as_ref())?;
+            TileBuffers {
+                fixed: byte_offsets_from_element_offsets(list.offsets(), value_size),
+                var: Some(values.fixed),
+                validity,
+            }
+        }
+        DataType::Utf8View => {
+            let views = array
+                .as_any()
+                .downcast_ref::<StringViewArray>()
+                .expect("Logic error: array does not match its own data type");
+            let (fixed, var) =
+                var_tile_from_values(views.iter().map(|v| v.unwrap_or("").as_bytes()));
+            TileBuffers {
+                fixed,
+                var: Some(var),
+                validity,
+            }
+        }
+        DataType::BinaryView => {
+            let views = array
+                .as_any()
+                .downcast_ref::<BinaryViewArray>()
+                .expect("Logic error: array does not match its own data type");
+            let (fixed, var) = var_tile_from_values(views.iter().map(|v| v.unwrap_or(&[])));
+            TileBuffers {
+                fixed,
+                var: Some(var),
+                validity,
+            }
+        }
+        other => return Err(FieldError::UnsupportedArrowDataType(other.clone())),
+    })
+}
+
+/// Converts `offsets`, whose unit is elements of size `value_size`, into raw
+/// TileDB byte offsets (`i64`, little-endian).
+fn byte_offsets_from_element_offsets(offsets: &OffsetBuffer<i64>, value_size: usize) -> Vec<u8> {
+    offsets
+        .iter()
+        .flat_map(|o| (o * value_size as i64).to_le_bytes())
+        .collect()
+}
+
+/// Builds the `(fixed, var)` tile buffers for variable-length `values`: the
+/// byte offset of each cell (plus one trailing offset for the end of the
+/// last cell) in `fixed`, and the concatenated cell bytes in `var`.
+fn var_tile_from_values<'a>(values: impl Iterator<Item = &'a [u8]>) -> (Vec<u8>, Vec<u8>) {
+    let mut var = Vec::new();
+    let mut offsets = vec![0i64];
+    for value in values {
+        var.extend_from_slice(value);
+        offsets.push(var.len() as i64);
+    }
+    let fixed = offsets.iter().flat_map(|o| o.to_le_bytes()).collect();
+    (fixed, var)
+}
+
 /// Returns a [PrimitiveArray] which contains the same contents as a [Tile]
 /// with the provided `validity`.
 ///
@@ -341,6 +816,23 @@ where
     ))
 }
 
+/// Packs a single Arrow string/binary "view": a 4-byte length, followed
+/// either by `data` inlined (if `data.len() <= 12`), or by a 4-byte prefix
+/// of `data` plus a buffer index (always `0`, the tile's var-length data
+/// buffer) and `offset` into that buffer (if `data.len() > 12`).
+fn byte_view(data: &[u8], offset: u32) -> u128 {
+    let mut view = [0u8; 16];
+    view[0..4].copy_from_slice(&(data.len() as u32).to_le_bytes());
+    if data.len() <= 12 {
+        view[4..4 + data.len()].copy_from_slice(data);
+    } else {
+        view[4..8].copy_from_slice(&data[0..4]);
+        view[8..12].copy_from_slice(&0u32.to_le_bytes());
+        view[12..16].copy_from_slice(&offset.to_le_bytes());
+    }
+    u128::from_le_bytes(view)
+}
+
 /// Returns an [OffsetBuffer] which represents the contents of the `[u8]`.
 fn to_offsets_buffer(value_field: &Field, bytes: &[u8]) -> Result<OffsetBuffer<i64>, OffsetsError> {
     let Some(value_size) = value_field.data_type().primitive_width() else {
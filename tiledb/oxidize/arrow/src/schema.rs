@@ -19,12 +19,14 @@
 //!
 //! As a guideline, the "array storage" schema should be used internally and
 //! the "array view" schema should be used for user endpoint APIs.
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::num::NonZeroU32;
 use std::str::Utf8Error;
 use std::sync::Arc;
 
 use arrow::datatypes::{
-    DataType as ArrowDataType, Field as ArrowField, Fields as ArrowFields, Schema,
+    DataType as ArrowDataType, Field as ArrowField, Fields as ArrowFields, Schema, TimeUnit,
 };
 use itertools::Itertools;
 use tiledb_cxx_interface::sm::array_schema::{ArraySchema, CellValNum, Field};
@@ -54,10 +56,48 @@ pub enum FieldError {
     InternalEnumerationNotFound(String),
     #[error("Enumeration name is not UTF-8")]
     EnumerationNameNotUtf8(Vec<u8>, Utf8Error),
+    #[error("Arrow data type has no corresponding TileDB data type: {0}")]
+    UnsupportedArrowDataType(ArrowDataType),
+    #[error("Fixed-size list length is not a valid cell val num: {0}")]
+    InvalidFixedSizeListLength(i32),
 }
 
 pub type Enumerations = HashMap<String, Option<Arc<dyn arrow::array::Array>>>;
 
+/// Resolves the value `(Datatype, CellValNum)` of an enumeration by name,
+/// without needing to load its variants (the type is co-located with storage,
+/// see [field_arrow_datatype]'s `WhichSchema::View` branch).
+///
+/// This follows the same pattern as DataFusion's logical-type registries:
+/// a type is resolved by name instead of being eagerly materialized.
+pub trait EnumerationTypeResolver {
+    /// Returns the previously-recorded value type of the enumeration named
+    /// `name`, if known.
+    fn resolve(&self, name: &str) -> Option<(Datatype, CellValNum)>;
+
+    /// Records the value type of the enumeration named `name`. Called by
+    /// [project_arrow] when it loads an enumeration's variants, so a later
+    /// call can resolve the same enumeration's type without reloading them.
+    fn record(&self, _name: &str, _datatype: Datatype, _cell_val_num: CellValNum) {}
+}
+
+/// An [EnumerationTypeResolver] with no prior knowledge, which learns
+/// enumeration types as they are loaded by [project_arrow]/[to_arrow].
+#[derive(Debug, Default)]
+pub struct EnumerationTypeCache(RefCell<HashMap<String, (Datatype, CellValNum)>>);
+
+impl EnumerationTypeResolver for EnumerationTypeCache {
+    fn resolve(&self, name: &str) -> Option<(Datatype, CellValNum)> {
+        self.0.borrow().get(name).copied()
+    }
+
+    fn record(&self, name: &str, datatype: Datatype, cell_val_num: CellValNum) {
+        self.0
+            .borrow_mut()
+            .insert(name.to_owned(), (datatype, cell_val_num));
+    }
+}
+
 /// Wraps a [Schema] for passing across the FFI boundary.
 pub struct ArrowArraySchema {
     pub schema: Arc<Schema>,
@@ -67,14 +107,49 @@ pub struct ArrowArraySchema {
 pub fn to_arrow(
     array_schema: &ArraySchema,
     which: WhichSchema,
+    resolver: &dyn EnumerationTypeResolver,
 ) -> Result<(Schema, Enumerations), Error> {
-    project_arrow(array_schema, which, |_: &Field| true)
+    project_arrow(array_schema, which, resolver, |_: &Field| true)
+}
+
+/// Like [to_arrow], but `var_len` selects whether variable-length
+/// string/binary fields are represented as `LargeUtf8`/`LargeBinary` or
+/// `Utf8View`/`BinaryView` (see [arrow_datatype_with_var_len_mode]).
+pub fn to_arrow_with_var_len_mode(
+    array_schema: &ArraySchema,
+    which: WhichSchema,
+    resolver: &dyn EnumerationTypeResolver,
+    var_len: VarLenMode,
+) -> Result<(Schema, Enumerations), Error> {
+    project_arrow_with_var_len_mode(array_schema, which, resolver, var_len, |_: &Field| true)
 }
 
 /// Returns a [Schema] which represents the physical field types of the selected fields from `array_schema`.
+///
+/// `resolver` supplies the value types of enumerations which have not yet
+/// been loaded in this call (see [field_arrow_datatype]); enumerations which
+/// this call does load are recorded into it, so a caller which reuses the
+/// same `resolver` across calls avoids reloading them.
 pub fn project_arrow<F>(
     array_schema: &ArraySchema,
     which: WhichSchema,
+    resolver: &dyn EnumerationTypeResolver,
+    select: F,
+) -> Result<(Schema, Enumerations), Error>
+where
+    F: Fn(&Field) -> bool,
+{
+    project_arrow_with_var_len_mode(array_schema, which, resolver, VarLenMode::List, select)
+}
+
+/// Like [project_arrow], but `var_len` selects whether variable-length
+/// string/binary fields are represented as `LargeUtf8`/`LargeBinary` or
+/// `Utf8View`/`BinaryView` (see [arrow_datatype_with_var_len_mode]).
+pub fn project_arrow_with_var_len_mode<F>(
+    array_schema: &ArraySchema,
+    which: WhichSchema,
+    resolver: &dyn EnumerationTypeResolver,
+    var_len: VarLenMode,
     select: F,
 ) -> Result<(Schema, Enumerations), Error>
 where
@@ -87,8 +162,9 @@ where
             let field_name = f
                 .name()
                 .map_err(|e| Error::NameNotUtf8(f.name_cxx().as_bytes().to_vec(), e))?;
-            let arrow_type = field_arrow_datatype(array_schema, which, &f)
-                .map_err(|e| Error::FieldError(field_name.to_owned(), e))?;
+            let arrow_type =
+                field_arrow_datatype_with_var_len_mode(array_schema, which, &f, resolver, var_len)
+                    .map_err(|e| Error::FieldError(field_name.to_owned(), e))?;
 
             // NB: fields can always be null due to schema evolution
             let arrow = ArrowField::new(field_name, arrow_type, true);
@@ -122,6 +198,7 @@ where
             if enumeration.is_null() {
                 Ok((e.to_owned(), None))
             } else {
+                resolver.record(e, enumeration.datatype(), enumeration.cell_val_num());
                 let a = unsafe {
                     // SAFETY: TODO comment
                     crate::enumeration::array_from_enumeration(&enumeration)
@@ -146,12 +223,32 @@ pub fn field_arrow_datatype(
     array_schema: &ArraySchema,
     which: WhichSchema,
     field: &Field,
+    resolver: &dyn EnumerationTypeResolver,
+) -> Result<ArrowDataType, FieldError> {
+    field_arrow_datatype_with_var_len_mode(array_schema, which, field, resolver, VarLenMode::List)
+}
+
+/// Like [field_arrow_datatype], but `var_len` selects whether variable-length
+/// string/binary fields are represented as `LargeUtf8`/`LargeBinary` or
+/// `Utf8View`/`BinaryView` (see [arrow_datatype_with_var_len_mode]).
+pub fn field_arrow_datatype_with_var_len_mode(
+    array_schema: &ArraySchema,
+    which: WhichSchema,
+    field: &Field,
+    resolver: &dyn EnumerationTypeResolver,
+    var_len: VarLenMode,
 ) -> Result<ArrowDataType, FieldError> {
     match which {
-        WhichSchema::Storage => arrow_datatype(field.datatype(), field.cell_val_num()),
+        WhichSchema::Storage => {
+            arrow_datatype_storage(field.datatype(), field.cell_val_num(), var_len)
+        }
         WhichSchema::View => {
             let Some(e_name) = field.enumeration_name_cxx() else {
-                return arrow_datatype(field.datatype(), field.cell_val_num());
+                return arrow_datatype_with_var_len_mode(
+                    field.datatype(),
+                    field.cell_val_num(),
+                    var_len,
+                );
             };
             if !array_schema.has_enumeration(e_name) {
                 return Err(FieldError::InternalEnumerationNotFound(
@@ -159,11 +256,30 @@ pub fn field_arrow_datatype(
                 ));
             }
 
+            if let Some((datatype, cell_val_num)) =
+                resolver.resolve(&e_name.to_string_lossy())
+            {
+                // The field's own storage type is the dictionary *key*
+                // (e.g. the `INT32` index TileDB stores per cell); the
+                // enumeration's resolved type is the dictionary *value*
+                // (e.g. the `UTF8` variant strings). This lets
+                // `record_batch::to_arrow_array` hand back a zero-copy
+                // `DictionaryArray` instead of requiring every consumer to
+                // decode keys to their variant values up front.
+                let key_type = arrow_primitive_datatype(field.datatype())?;
+                let value_type = arrow_datatype_with_var_len_mode(datatype, cell_val_num, var_len)?;
+                return Ok(ArrowDataType::Dictionary(
+                    Box::new(key_type),
+                    Box::new(value_type),
+                ));
+            }
+
             // NB: This branch is reached from `session::parse_expr` which requires
             // a schema in order to parse the text into logical expression.
             // However, we may not have the enumeration loaded, and without
             // loading it we don't know the type (since the type is co-located
-            // in storage with the variants).
+            // in storage with the variants), unless `resolver` already learned
+            // it from a previous call.
             // We should not need to load all enumerations (potentially expensive)
             // in order to parse text.
             // We also should not error here because then nothing can be parsed
@@ -184,9 +300,74 @@ pub fn field_arrow_datatype(
     }
 }
 
+/// Returns `datatype`'s raw on-disk physical [ArrowDataType], collapsing
+/// every `DATETIME_*`/`TIME_*` variant onto its `Int64` backing
+/// representation regardless of unit, so a storage-schema read stays
+/// bit-identical to the tile bytes. This is what [field_arrow_datatype]'s
+/// `WhichSchema::Storage` branch uses; `WhichSchema::View` instead uses
+/// [arrow_primitive_datatype]'s natively-typed `Timestamp`/`Time32`/
+/// `Time64`/`Date32` mapping, since it targets DataFusion/Arrow consumers
+/// rather than the raw tile buffers.
+fn arrow_datatype_storage(
+    datatype: Datatype,
+    cell_val_num: CellValNum,
+    var_len: VarLenMode,
+) -> Result<ArrowDataType, FieldError> {
+    let datatype = if is_temporal_datatype(datatype) {
+        Datatype::INT64
+    } else {
+        datatype
+    };
+    arrow_datatype_with_var_len_mode(datatype, cell_val_num, var_len)
+}
+
+/// Returns whether `datatype` is one of the `DATETIME_*`/`TIME_*` variants
+/// [arrow_primitive_datatype] maps to a native Arrow temporal type rather
+/// than falling back to `Int64` (see [arrow_datatype_storage]).
+fn is_temporal_datatype(datatype: Datatype) -> bool {
+    matches!(
+        datatype,
+        Datatype::DATETIME_SEC
+            | Datatype::DATETIME_MS
+            | Datatype::DATETIME_US
+            | Datatype::DATETIME_NS
+            | Datatype::DATETIME_DAY
+            | Datatype::TIME_SEC
+            | Datatype::TIME_MS
+            | Datatype::TIME_US
+            | Datatype::TIME_NS
+    )
+}
+
+/// Selects the Arrow representation used for variable-length string/binary
+/// fields by [arrow_datatype_with_var_len_mode].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VarLenMode {
+    /// `LargeUtf8`/`LargeBinary`, backed by a full offsets buffer. This is
+    /// the default, and what [arrow_datatype] produces.
+    #[default]
+    List,
+    /// `Utf8View`/`BinaryView`, avoiding the offsets buffer and any copy of
+    /// values which fit inline in the view (12 bytes or fewer). See
+    /// [crate::record_batch::to_arrow_array] for how these are constructed.
+    View,
+}
+
 pub fn arrow_datatype(
     datatype: Datatype,
     cell_val_num: CellValNum,
+) -> Result<ArrowDataType, FieldError> {
+    arrow_datatype_with_var_len_mode(datatype, cell_val_num, VarLenMode::List)
+}
+
+/// Like [arrow_datatype], but `var_len` selects whether variable-length
+/// string/binary fields are represented as `LargeUtf8`/`LargeBinary`
+/// (`VarLenMode::List`) or `Utf8View`/`BinaryView` (`VarLenMode::View`).
+/// Other fields are unaffected by `var_len`.
+pub fn arrow_datatype_with_var_len_mode(
+    datatype: Datatype,
+    cell_val_num: CellValNum,
+    var_len: VarLenMode,
 ) -> Result<ArrowDataType, FieldError> {
     match cell_val_num {
         CellValNum::Single => Ok(arrow_primitive_datatype(datatype)?),
@@ -202,54 +383,134 @@ pub fn arrow_datatype(
                 Err(FieldError::InvalidCellValNum(cell_val_num))
             }
         }
-        CellValNum::Var => {
-            if matches!(datatype, Datatype::STRING_ASCII | Datatype::STRING_UTF8) {
+        CellValNum::Var => match (datatype, var_len) {
+            (Datatype::STRING_ASCII | Datatype::STRING_UTF8 | Datatype::GEOM_WKT, VarLenMode::List) => {
                 Ok(ArrowDataType::LargeUtf8)
-            } else {
-                let value_type = arrow_primitive_datatype(datatype)?;
+            }
+            (Datatype::STRING_ASCII | Datatype::STRING_UTF8 | Datatype::GEOM_WKT, VarLenMode::View) => {
+                Ok(ArrowDataType::Utf8View)
+            }
+            (Datatype::BLOB | Datatype::GEOM_WKB, VarLenMode::List) => Ok(ArrowDataType::LargeBinary),
+            (Datatype::BLOB | Datatype::GEOM_WKB, VarLenMode::View) => Ok(ArrowDataType::BinaryView),
+            (other, _) => {
+                let value_type = arrow_primitive_datatype(other)?;
                 Ok(ArrowDataType::LargeList(Arc::new(
                     ArrowField::new_list_field(value_type, false),
                 )))
             }
+        },
+    }
+}
+
+/// Returns the `(Datatype, CellValNum)` which `arrow` was produced from by
+/// [arrow_datatype], where possible. Several `Datatype`s collapse onto the
+/// same physical Arrow type (e.g. all of the sub-second-granularity-less
+/// `DATETIME_*`/`TIME_*` variants collapse onto `Int64`), so this is not a
+/// true inverse for those; it picks the most specific, commonly-used variant.
+pub fn datatype_from_arrow(arrow: &ArrowDataType) -> Result<(Datatype, CellValNum), FieldError> {
+    match arrow {
+        ArrowDataType::FixedSizeList(value_field, len) => {
+            let datatype = primitive_datatype_from_arrow(value_field.data_type())?;
+            let nz = u32::try_from(*len)
+                .ok()
+                .and_then(NonZeroU32::new)
+                .ok_or(FieldError::InvalidFixedSizeListLength(*len))?;
+            Ok((datatype, CellValNum::Fixed(nz)))
+        }
+        ArrowDataType::LargeList(value_field) => {
+            let datatype = primitive_datatype_from_arrow(value_field.data_type())?;
+            Ok((datatype, CellValNum::Var))
         }
+        ArrowDataType::LargeUtf8 | ArrowDataType::Utf8 => {
+            Ok((Datatype::STRING_UTF8, CellValNum::Var))
+        }
+        ArrowDataType::LargeBinary | ArrowDataType::Binary => Ok((Datatype::BLOB, CellValNum::Var)),
+        // The dictionary *key* is what TileDB actually stores per cell (see
+        // [field_arrow_datatype]'s `WhichSchema::View` branch); the value
+        // type lives in the field's attached `Enumeration`, which a caller
+        // reconstructing an `ArraySchema` sets separately.
+        ArrowDataType::Dictionary(key_type, _) => datatype_from_arrow(key_type),
+        other => Ok((primitive_datatype_from_arrow(other)?, CellValNum::Single)),
     }
 }
 
+/// Returns the `Datatype` whose single-value physical representation is `arrow`.
+fn primitive_datatype_from_arrow(arrow: &ArrowDataType) -> Result<Datatype, FieldError> {
+    Ok(match arrow {
+        ArrowDataType::Int8 => Datatype::INT8,
+        ArrowDataType::Int16 => Datatype::INT16,
+        ArrowDataType::Int32 => Datatype::INT32,
+        ArrowDataType::Int64 => Datatype::INT64,
+        ArrowDataType::UInt8 => Datatype::UINT8,
+        ArrowDataType::UInt16 => Datatype::UINT16,
+        ArrowDataType::UInt32 => Datatype::UINT32,
+        ArrowDataType::UInt64 => Datatype::UINT64,
+        ArrowDataType::Float32 => Datatype::FLOAT32,
+        ArrowDataType::Float64 => Datatype::FLOAT64,
+        // No `ArrowDataType::Boolean => Datatype::BOOL` arm: that would make
+        // this the inverse of `arrow_primitive_datatype`, but that function
+        // maps `Datatype::BOOL` to `UInt8`, not `Boolean`, since there's no
+        // zero-copy conversion between TileDB's one-byte-per-cell BOOL and
+        // Arrow's bit-packed `Boolean` array. Inferring `Datatype::BOOL` from
+        // an incoming bit-packed `Boolean` array here would silently
+        // reinterpret its buffer as `UInt8` later on.
+        ArrowDataType::Timestamp(TimeUnit::Second, _) => Datatype::DATETIME_SEC,
+        ArrowDataType::Timestamp(TimeUnit::Millisecond, _) => Datatype::DATETIME_MS,
+        ArrowDataType::Timestamp(TimeUnit::Microsecond, _) => Datatype::DATETIME_US,
+        ArrowDataType::Timestamp(TimeUnit::Nanosecond, _) => Datatype::DATETIME_NS,
+        ArrowDataType::Time32(TimeUnit::Second) => Datatype::TIME_SEC,
+        ArrowDataType::Time32(TimeUnit::Millisecond) => Datatype::TIME_MS,
+        ArrowDataType::Time64(TimeUnit::Microsecond) => Datatype::TIME_US,
+        ArrowDataType::Time64(TimeUnit::Nanosecond) => Datatype::TIME_NS,
+        ArrowDataType::Date32 => Datatype::DATETIME_DAY,
+        other => return Err(FieldError::UnsupportedArrowDataType(other.clone())),
+    })
+}
+
 /// Returns an [ArrowDataType] which represents the physical type of a single value of `datatype`.
 pub fn arrow_primitive_datatype(datatype: Datatype) -> Result<ArrowDataType, FieldError> {
     Ok(match datatype {
         Datatype::INT8 => ArrowDataType::Int8,
         Datatype::INT16 => ArrowDataType::Int16,
         Datatype::INT32 => ArrowDataType::Int32,
+        // Coarser-than-second and finer-than-nanosecond granularities have no
+        // matching Arrow `TimeUnit`, so they fall back to their raw integer
+        // backing representation.
         Datatype::INT64
         | Datatype::DATETIME_YEAR
         | Datatype::DATETIME_MONTH
         | Datatype::DATETIME_WEEK
-        | Datatype::DATETIME_DAY
         | Datatype::DATETIME_HR
         | Datatype::DATETIME_MIN
-        | Datatype::DATETIME_SEC
-        | Datatype::DATETIME_MS
-        | Datatype::DATETIME_US
-        | Datatype::DATETIME_NS
         | Datatype::DATETIME_PS
         | Datatype::DATETIME_FS
         | Datatype::DATETIME_AS
         | Datatype::TIME_HR
         | Datatype::TIME_MIN
-        | Datatype::TIME_SEC
-        | Datatype::TIME_MS
-        | Datatype::TIME_US
-        | Datatype::TIME_NS
         | Datatype::TIME_PS
         | Datatype::TIME_FS
         | Datatype::TIME_AS => ArrowDataType::Int64,
-        Datatype::UINT8
+        Datatype::DATETIME_SEC => ArrowDataType::Timestamp(TimeUnit::Second, None),
+        Datatype::DATETIME_MS => ArrowDataType::Timestamp(TimeUnit::Millisecond, None),
+        Datatype::DATETIME_US => ArrowDataType::Timestamp(TimeUnit::Microsecond, None),
+        Datatype::DATETIME_NS => ArrowDataType::Timestamp(TimeUnit::Nanosecond, None),
+        Datatype::DATETIME_DAY => ArrowDataType::Date32,
+        // `Time32`/`Time64` is a choice Arrow makes based on unit, not us.
+        Datatype::TIME_SEC => ArrowDataType::Time32(TimeUnit::Second),
+        Datatype::TIME_MS => ArrowDataType::Time32(TimeUnit::Millisecond),
+        Datatype::TIME_US => ArrowDataType::Time64(TimeUnit::Microsecond),
+        Datatype::TIME_NS => ArrowDataType::Time64(TimeUnit::Nanosecond),
+        // Arrow's `Boolean`/`BooleanArray` is bit-packed, but TileDB stores
+        // BOOL as one byte per cell, so it can't go through the same
+        // zero-copy arm as the other primitives below without an actual
+        // bit-packing conversion. Keep it mapped to `UInt8` until `to_arrow_array`/
+        // `from_arrow_array` gain that conversion.
+        Datatype::BOOL
+        | Datatype::UINT8
         | Datatype::STRING_ASCII
         | Datatype::STRING_UTF8
         | Datatype::ANY
         | Datatype::BLOB
-        | Datatype::BOOL
         | Datatype::GEOM_WKB
         | Datatype::GEOM_WKT => ArrowDataType::UInt8,
         Datatype::UINT16 | Datatype::STRING_UTF16 | Datatype::STRING_UCS2 => ArrowDataType::UInt16,
@@ -270,3 +531,56 @@ pub fn arrow_primitive_datatype(datatype: Datatype) -> Result<ArrowDataType, Fie
         }
     })
 }
+
+/// Recursively rewrites the child [ArrowField] names of `array`'s data type
+/// to match `target`, preserving nullability and metadata otherwise.
+///
+/// Arrow's cast kernel treats two `LargeList`/`FixedSizeList` types with
+/// differently-named child fields (e.g. `item` vs `element`) as distinct
+/// types, even when the element datatype is identical. That rejects casts
+/// between expression results computed against tiles whose list field came
+/// from different schema evolutions. Calling this before
+/// `compute::kernels::cast::cast` works around it by renaming the source
+/// array's child fields up front, so the cast kernel only has to compare
+/// datatypes, not field names.
+///
+/// `Struct`/`Map` are not handled yet; `array` is returned unchanged for any
+/// shape other than `LargeList`/`FixedSizeList`, including when `target`
+/// doesn't otherwise match `array`'s shape -- the cast kernel is left to
+/// report that mismatch with its usual error.
+pub fn rename_nested_fields(
+    array: arrow::array::ArrayRef,
+    target: &ArrowDataType,
+) -> arrow::array::ArrayRef {
+    use arrow::array::{Array, FixedSizeListArray, GenericListArray};
+
+    match (array.data_type(), target) {
+        (ArrowDataType::LargeList(_), ArrowDataType::LargeList(target_field)) => {
+            let Some(list) = array.as_any().downcast_ref::<GenericListArray<i64>>() else {
+                return array;
+            };
+            let values = rename_nested_fields(Arc::clone(list.values()), target_field.data_type());
+            Arc::new(GenericListArray::<i64>::new(
+                Arc::clone(target_field),
+                list.offsets().clone(),
+                values,
+                list.nulls().cloned(),
+            ))
+        }
+        (ArrowDataType::FixedSizeList(_, size), ArrowDataType::FixedSizeList(target_field, target_size))
+            if size == target_size =>
+        {
+            let Some(list) = array.as_any().downcast_ref::<FixedSizeListArray>() else {
+                return array;
+            };
+            let values = rename_nested_fields(Arc::clone(list.values()), target_field.data_type());
+            Arc::new(FixedSizeListArray::new(
+                Arc::clone(target_field),
+                *size,
+                values,
+                list.nulls().cloned(),
+            ))
+        }
+        _ => array,
+    }
+}
@@ -2,8 +2,10 @@
 mod ffi {
     #[namespace = "tiledb::sm"]
     extern "C++" {
+        type ArrayType = crate::sm::enums::ArrayType;
         type Datatype = crate::sm::enums::Datatype;
         type Layout = crate::sm::enums::Layout;
+        type MemoryTracker = crate::common::memory_tracker::MemoryTracker;
     }
 
     #[namespace = "tiledb::oxidize::sm::attribute"]
@@ -29,6 +31,9 @@ mod ffi {
         #[namespace = "tiledb::oxidize::sm::attribute"]
         fn enumeration_name_cxx(attr: &Attribute) -> *const CxxString;
 
+        #[namespace = "tiledb::oxidize::sm::attribute"]
+        fn set_enumeration_name_cxx(attr: Pin<&mut Attribute>, name: &CxxString);
+
         fn set_cell_val_num(self: Pin<&mut Attribute>, cell_val_num: u32);
     }
 
@@ -77,6 +82,12 @@ mod ffi {
 
         #[cxx_name = "type"]
         fn datatype(&self) -> Datatype;
+
+        #[cxx_name = "data"]
+        fn data_cxx(&self) -> &[u8];
+
+        #[cxx_name = "offsets"]
+        fn offsets_cxx(&self) -> &[u64];
     }
 
     #[namespace = "tiledb::sm"]
@@ -110,16 +121,59 @@ mod ffi {
             attribute: SharedPtr<ConstAttribute>,
             check_special: bool,
         );
+        fn add_enumeration(self: Pin<&mut ArraySchema>, enumeration: SharedPtr<Enumeration>);
         fn set_tile_order(self: Pin<&mut ArraySchema>, order: Layout);
         fn set_cell_order(self: Pin<&mut ArraySchema>, order: Layout);
         fn set_capacity(self: Pin<&mut ArraySchema>, capacity: u64);
         fn set_allows_dups(self: Pin<&mut ArraySchema>, allows_dups: bool);
     }
 
+    #[namespace = "tiledb::oxidize::sm::array_schema"]
+    unsafe extern "C++" {
+        include!("tiledb/oxidize/cxx-interface/cc/array_schema.h");
+
+        fn new_attribute(name: &CxxString, datatype: Datatype, nullable: bool) -> UniquePtr<Attribute>;
+
+        fn new_dimension(
+            name: &CxxString,
+            datatype: Datatype,
+            memory_tracker: SharedPtr<MemoryTracker>,
+        ) -> UniquePtr<Dimension>;
+
+        fn new_domain(memory_tracker: SharedPtr<MemoryTracker>) -> UniquePtr<Domain>;
+
+        fn new_array_schema(
+            array_type: ArrayType,
+            memory_tracker: SharedPtr<MemoryTracker>,
+        ) -> UniquePtr<ArraySchema>;
+
+        fn attribute_to_shared(attribute: UniquePtr<Attribute>) -> SharedPtr<Attribute>;
+        fn dimension_to_shared(dimension: UniquePtr<Dimension>) -> SharedPtr<Dimension>;
+        fn domain_to_shared(domain: UniquePtr<Domain>) -> SharedPtr<Domain>;
+        fn array_schema_to_shared(array_schema: UniquePtr<ArraySchema>) -> SharedPtr<ArraySchema>;
+
+        #[namespace = "tiledb::oxidize::sm::attribute"]
+        fn as_const_attribute(attribute: SharedPtr<Attribute>) -> SharedPtr<ConstAttribute>;
+
+        /// Materializes a new [Enumeration] whose variants are `data`
+        /// (sliced by `offsets`, when `cell_val_num` is var-sized). Unlike
+        /// the other constructors above, an [Enumeration] is immutable once
+        /// created, so this directly returns a `SharedPtr` rather than a
+        /// `UniquePtr` staged for further mutation.
+        fn new_enumeration(
+            name: &CxxString,
+            datatype: Datatype,
+            cell_val_num: u32,
+            data: &[u8],
+            offsets: &[u64],
+        ) -> SharedPtr<Enumeration>;
+    }
+
     impl SharedPtr<Attribute> {}
     impl SharedPtr<Dimension> {}
     impl SharedPtr<Domain> {}
     impl SharedPtr<ArraySchema> {}
+    impl SharedPtr<Enumeration> {}
     impl UniquePtr<Attribute> {}
     impl UniquePtr<Dimension> {}
     impl UniquePtr<Domain> {}
@@ -133,9 +187,14 @@ use std::str::Utf8Error;
 
 use num_traits::ToBytes;
 
-pub use ffi::{ArraySchema, Attribute, ConstAttribute, Datatype, Dimension, Domain, Enumeration};
+pub use ffi::{
+    ArraySchema, ArrayType, Attribute, ConstAttribute, Datatype, Dimension, Domain, Enumeration,
+    Layout, new_enumeration,
+};
 
-#[derive(Debug)]
+use crate::common::memory_tracker::MemoryTracker;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CellValNum {
     /// Cells of this field each contain exactly one value.
     Single,
@@ -251,6 +310,15 @@ impl Attribute {
         let cxx = unsafe { &*ptr };
         Some(cxx.to_str())
     }
+
+    /// Binds this attribute to the schema enumeration named `name`. The
+    /// enumeration itself must still be attached to the schema separately
+    /// via [ArraySchema::add_enumeration]; this only records the name the
+    /// attribute's values are indices into.
+    pub fn set_enumeration_name(self: Pin<&mut Self>, name: &str) {
+        cxx::let_cxx_string!(name = name);
+        ffi::set_enumeration_name_cxx(self, &name)
+    }
 }
 
 impl Debug for Attribute {
@@ -319,6 +387,22 @@ impl Enumeration {
         // SAFETY: non-zero would have been validated by the ArraySchema
         CellValNum::from_cxx(cxx).unwrap()
     }
+
+    /// Returns the raw bytes of this enumeration's variants, concatenated
+    /// for var-sized cells or packed end-to-end for fixed-sized cells.
+    pub fn data(&self) -> &[u8] {
+        self.data_cxx()
+    }
+
+    /// Returns the cumulative byte offsets of each variant within
+    /// [Self::data], or `None` if [Self::cell_val_num] is not
+    /// [CellValNum::Var].
+    pub fn offsets(&self) -> Option<&[u64]> {
+        match self.cell_val_num() {
+            CellValNum::Var => Some(self.offsets_cxx()),
+            CellValNum::Single | CellValNum::Fixed(_) => None,
+        }
+    }
 }
 
 impl ArraySchema {
@@ -366,3 +450,207 @@ impl ArraySchema {
             .chain(self.attributes().map(Field::Attribute))
     }
 }
+
+/// Errors which prevent an [ArraySchemaBuilder] or [DomainBuilder] from
+/// producing a usable schema.
+#[derive(Debug, thiserror::Error)]
+pub enum SchemaBuildError {
+    #[error("array schema must have a domain with at least one dimension")]
+    NoDimensions,
+    #[error("field name '{0}' is used by more than one attribute or dimension")]
+    DuplicateFieldName(String),
+    #[error(transparent)]
+    Cxx(#[from] cxx::Exception),
+}
+
+/// Builds a new [Dimension] from scratch. Configure it with [Self::domain]
+/// and [Self::tile_extent], then hand the result of [Self::build] to
+/// [DomainBuilder::add_dimension].
+pub struct DimensionBuilder {
+    dimension: cxx::UniquePtr<Dimension>,
+}
+
+impl DimensionBuilder {
+    pub fn new(name: &str, datatype: Datatype, memory_tracker: cxx::SharedPtr<MemoryTracker>) -> Self {
+        cxx::let_cxx_string!(name = name);
+        Self {
+            dimension: ffi::new_dimension(&name, datatype, memory_tracker),
+        }
+    }
+
+    /// Sets the `[lower_bound, upper_bound]` domain of this dimension.
+    pub fn domain<T>(mut self, lower_bound: T, upper_bound: T) -> Result<Self, SchemaBuildError>
+    where
+        T: ToBytes,
+    {
+        self.dimension.pin_mut().set_domain(lower_bound, upper_bound)?;
+        Ok(self)
+    }
+
+    /// Sets the tile extent of this dimension.
+    pub fn tile_extent<T>(mut self, extent: T) -> Result<Self, SchemaBuildError>
+    where
+        T: ToBytes,
+    {
+        self.dimension.pin_mut().set_tile_extent(extent)?;
+        Ok(self)
+    }
+
+    pub fn build(self) -> cxx::SharedPtr<Dimension> {
+        ffi::dimension_to_shared(self.dimension)
+    }
+}
+
+/// Builds a new [Attribute] from scratch. Configure it with
+/// [Self::cell_val_num], then hand the result of [Self::build] to
+/// [ArraySchemaBuilder::add_attribute].
+pub struct AttributeBuilder {
+    attribute: cxx::UniquePtr<Attribute>,
+}
+
+impl AttributeBuilder {
+    pub fn new(name: &str, datatype: Datatype, nullable: bool) -> Self {
+        cxx::let_cxx_string!(name = name);
+        Self {
+            attribute: ffi::new_attribute(&name, datatype, nullable),
+        }
+    }
+
+    /// Sets the number of values held by each cell of this attribute.
+    /// [CellValNum] cannot represent zero, so unlike the raw FFI setter this
+    /// cannot produce an invalid cell val num.
+    pub fn cell_val_num(mut self, cell_val_num: CellValNum) -> Self {
+        self.attribute
+            .pin_mut()
+            .set_cell_val_num(u32::from(cell_val_num));
+        self
+    }
+
+    pub fn build(self) -> cxx::SharedPtr<Attribute> {
+        ffi::attribute_to_shared(self.attribute)
+    }
+}
+
+/// Builds a new [Domain] from scratch by collecting dimensions built with
+/// [DimensionBuilder]. Hand the result of [Self::build] to
+/// [ArraySchemaBuilder::domain].
+pub struct DomainBuilder {
+    domain: cxx::UniquePtr<Domain>,
+    num_dimensions: usize,
+}
+
+impl DomainBuilder {
+    pub fn new(memory_tracker: cxx::SharedPtr<MemoryTracker>) -> Self {
+        Self {
+            domain: ffi::new_domain(memory_tracker),
+            num_dimensions: 0,
+        }
+    }
+
+    pub fn add_dimension(mut self, dimension: cxx::SharedPtr<Dimension>) -> Self {
+        self.domain.pin_mut().add_dimension(dimension);
+        self.num_dimensions += 1;
+        self
+    }
+
+    /// Fails with [SchemaBuildError::NoDimensions] if no dimension was added.
+    pub fn build(self) -> Result<cxx::SharedPtr<Domain>, SchemaBuildError> {
+        if self.num_dimensions == 0 {
+            return Err(SchemaBuildError::NoDimensions);
+        }
+        Ok(ffi::domain_to_shared(self.domain))
+    }
+}
+
+/// Builds a new [ArraySchema] from scratch, using [CellValNum], [Datatype]
+/// and [Layout] rather than the raw integers the FFI setters take. Assembles
+/// a [Domain] (see [DomainBuilder]) and the schema's attributes into a
+/// complete schema, rejecting a domain/attribute name collision or a missing
+/// domain rather than handing an invalid schema across the bridge.
+pub struct ArraySchemaBuilder {
+    schema: cxx::UniquePtr<ArraySchema>,
+    field_names: std::collections::HashSet<String>,
+    has_domain: bool,
+}
+
+impl ArraySchemaBuilder {
+    pub fn new(array_type: ArrayType, memory_tracker: cxx::SharedPtr<MemoryTracker>) -> Self {
+        Self {
+            schema: ffi::new_array_schema(array_type, memory_tracker),
+            field_names: std::collections::HashSet::new(),
+            has_domain: false,
+        }
+    }
+
+    /// Sets this schema's domain. Fails if any of the domain's dimension
+    /// names collide with a field already added to this schema.
+    pub fn domain(mut self, domain: cxx::SharedPtr<Domain>) -> Result<Self, SchemaBuildError> {
+        for dim in domain.as_ref().unwrap().dimensions() {
+            let name = dim.name().to_str().unwrap().to_owned();
+            self.register_field_name(name)?;
+        }
+        self.schema.pin_mut().set_domain(domain)?;
+        self.has_domain = true;
+        Ok(self)
+    }
+
+    /// Adds an attribute to this schema. Fails if its name collides with a
+    /// field already added to this schema.
+    pub fn add_attribute(mut self, attribute: cxx::SharedPtr<Attribute>) -> Result<Self, SchemaBuildError> {
+        let name = attribute.as_ref().unwrap().name().to_str().unwrap().to_owned();
+        self.register_field_name(name)?;
+
+        self.schema
+            .pin_mut()
+            .add_attribute(ffi::as_const_attribute(attribute), false);
+        Ok(self)
+    }
+
+    /// Attaches an enumeration to this schema. It is only usable by an
+    /// attribute once that attribute is bound to it by name, e.g. via
+    /// [Attribute::set_enumeration_name] before calling [Self::add_attribute].
+    pub fn add_enumeration(mut self, enumeration: cxx::SharedPtr<Enumeration>) -> Self {
+        self.schema.pin_mut().add_enumeration(enumeration);
+        self
+    }
+
+    pub fn tile_order(mut self, order: Layout) -> Self {
+        self.schema.pin_mut().set_tile_order(order);
+        self
+    }
+
+    pub fn cell_order(mut self, order: Layout) -> Self {
+        self.schema.pin_mut().set_cell_order(order);
+        self
+    }
+
+    pub fn capacity(mut self, capacity: u64) -> Self {
+        self.schema.pin_mut().set_capacity(capacity);
+        self
+    }
+
+    pub fn allows_dups(mut self, allows_dups: bool) -> Self {
+        self.schema.pin_mut().set_allows_dups(allows_dups);
+        self
+    }
+
+    // `to_str().unwrap()` above is safe because field names passed to this
+    // builder (via `DimensionBuilder::new`/`AttributeBuilder::new`) are
+    // always constructed from a `&str`, so the round trip through
+    // `CxxString` is always valid UTF-8.
+    fn register_field_name(&mut self, name: String) -> Result<(), SchemaBuildError> {
+        if !self.field_names.insert(name.clone()) {
+            return Err(SchemaBuildError::DuplicateFieldName(name));
+        }
+        Ok(())
+    }
+
+    /// Fails with [SchemaBuildError::NoDimensions] if [Self::domain] was
+    /// never called.
+    pub fn build(self) -> Result<cxx::SharedPtr<ArraySchema>, SchemaBuildError> {
+        if !self.has_domain {
+            return Err(SchemaBuildError::NoDimensions);
+        }
+        Ok(ffi::array_schema_to_shared(self.schema))
+    }
+}
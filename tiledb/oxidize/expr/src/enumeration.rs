@@ -0,0 +1,246 @@
+//! Rewrites a logical expression parsed against an array's "view" schema
+//! (enumeration value types, see `tiledb_arrow::schema`'s module docs) into
+//! an equivalent expression over the "storage" schema (enumeration key
+//! types), by resolving comparisons against enumerated columns into
+//! comparisons against the matching enumeration key(s).
+//!
+//! Only the enumerations actually referenced by columns in the expression
+//! are materialized: a schema can have many enumerations, and most
+//! predicates only touch a handful of them.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow::array::Array as ArrowArray;
+use datafusion::common::tree_node::{Transformed, TreeNode, TreeNodeRewriter};
+use datafusion::common::{Column, DataFusionError, ScalarValue};
+use datafusion::logical_expr::expr::InList;
+use datafusion::logical_expr::{BinaryExpr, Expr, Operator};
+use tiledb_cxx_interface::sm::array_schema::{ArraySchema, Field};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Expression error: {0}")]
+    Expr(#[from] DataFusionError),
+}
+
+/// Rewrites `expr`, which was parsed against `schema`'s "view" schema, into
+/// an equivalent expression over the "storage" schema.
+///
+/// Columns which are not enumerated, and expression nodes which do not
+/// directly compare an enumerated column to a literal, pass through
+/// unchanged. A comparison against a literal which is not a current variant
+/// of the enumeration is resolved to a constant (`=`/`IN` become `false`,
+/// `!=`/`NOT IN` become `true`) rather than an error, since this is a
+/// perfectly valid (if never-true) query.
+pub fn rewrite_view_to_storage(expr: Expr, schema: &ArraySchema) -> Result<Expr, Error> {
+    let mut rewriter = EnumerationRewriter {
+        schema,
+        loaded: HashMap::new(),
+    };
+    Ok(expr.rewrite(&mut rewriter)?.data)
+}
+
+/// Materialized variants of the enumerations referenced so far, keyed by
+/// enumeration name. `None` means the schema declares the enumeration but
+/// its variants have not been loaded, in which case literals compared
+/// against it cannot be resolved to a key.
+type LoadedEnumerations = HashMap<String, Option<Arc<dyn ArrowArray>>>;
+
+struct EnumerationRewriter<'a> {
+    schema: &'a ArraySchema,
+    loaded: LoadedEnumerations,
+}
+
+impl TreeNodeRewriter for EnumerationRewriter<'_> {
+    type Node = Expr;
+
+    fn f_up(&mut self, node: Expr) -> Result<Transformed<Expr>, DataFusionError> {
+        match node {
+            Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
+                self.rewrite_binary(left, op, right)
+            }
+            Expr::InList(in_list) => self.rewrite_in_list(in_list),
+            other => Ok(Transformed::no(other)),
+        }
+    }
+}
+
+impl EnumerationRewriter<'_> {
+    fn rewrite_binary(
+        &mut self,
+        left: Box<Expr>,
+        op: Operator,
+        right: Box<Expr>,
+    ) -> Result<Transformed<Expr>, DataFusionError> {
+        if !matches!(op, Operator::Eq | Operator::NotEq) {
+            return Ok(Transformed::no(Expr::BinaryExpr(BinaryExpr {
+                left,
+                op,
+                right,
+            })));
+        }
+
+        let (column, literal, literal_on_right) = match (left.as_ref(), right.as_ref()) {
+            (Expr::Column(c), Expr::Literal(v)) => (c, v, true),
+            (Expr::Literal(v), Expr::Column(c)) => (c, v, false),
+            _ => {
+                return Ok(Transformed::no(Expr::BinaryExpr(BinaryExpr {
+                    left,
+                    op,
+                    right,
+                })));
+            }
+        };
+
+        let Some((field, variants)) = self.enumerated_field(column)? else {
+            return Ok(Transformed::no(Expr::BinaryExpr(BinaryExpr {
+                left,
+                op,
+                right,
+            })));
+        };
+
+        let replacement = match find_key(variants, literal) {
+            Some(key) => {
+                let key_literal = Expr::Literal(key_scalar(&field, key)?);
+                let (new_left, new_right) = if literal_on_right {
+                    (Box::new(Expr::Column(column.clone())), Box::new(key_literal))
+                } else {
+                    (Box::new(key_literal), Box::new(Expr::Column(column.clone())))
+                };
+                Expr::BinaryExpr(BinaryExpr {
+                    left: new_left,
+                    op,
+                    right: new_right,
+                })
+            }
+            None => Expr::Literal(ScalarValue::Boolean(Some(op == Operator::NotEq))),
+        };
+        Ok(Transformed::yes(replacement))
+    }
+
+    fn rewrite_in_list(&mut self, in_list: InList) -> Result<Transformed<Expr>, DataFusionError> {
+        let InList {
+            expr,
+            list,
+            negated,
+        } = in_list;
+
+        let Expr::Column(ref column) = *expr else {
+            return Ok(Transformed::no(Expr::InList(InList {
+                expr,
+                list,
+                negated,
+            })));
+        };
+
+        let Some((field, variants)) = self.enumerated_field(column)? else {
+            return Ok(Transformed::no(Expr::InList(InList {
+                expr,
+                list,
+                negated,
+            })));
+        };
+
+        let mut keys = Vec::with_capacity(list.len());
+        for item in &list {
+            let Expr::Literal(ref literal) = item else {
+                // not a literal; leave the whole list alone rather than
+                // partially rewriting it
+                return Ok(Transformed::no(Expr::InList(InList {
+                    expr,
+                    list,
+                    negated,
+                })));
+            };
+            if let Some(key) = find_key(variants, literal) {
+                keys.push(Expr::Literal(key_scalar(&field, key)?));
+            }
+        }
+
+        if keys.is_empty() {
+            // none of the members are current variants of the enumeration:
+            // `IN ()` is always false, `NOT IN ()` is always true
+            return Ok(Transformed::yes(Expr::Literal(ScalarValue::Boolean(Some(
+                negated,
+            )))));
+        }
+
+        Ok(Transformed::yes(Expr::InList(InList {
+            expr,
+            list: keys,
+            negated,
+        })))
+    }
+
+    /// Returns the enumeration's field and (if loaded) its materialized
+    /// variants, if `column` refers to an enumerated field of `self.schema`.
+    fn enumerated_field(
+        &mut self,
+        column: &Column,
+    ) -> Result<Option<(Field<'_>, &Option<Arc<dyn ArrowArray>>)>, DataFusionError> {
+        let Some(field) = self.schema.field(&column.name) else {
+            return Ok(None);
+        };
+        let Some(ename) = field.enumeration_name() else {
+            return Ok(None);
+        };
+        let ename = ename.map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+        if !self.loaded.contains_key(ename) {
+            let variants = if self.schema.has_enumeration(ename) {
+                let enumeration = self.schema.enumeration(ename);
+                Some(unsafe {
+                    // SAFETY: the variants are consumed (compared, or cast to
+                    // a key) within this rewrite and never returned to the
+                    // caller, so they do not outlive `enumeration`.
+                    tiledb_arrow::enumeration::array_from_enumeration(&enumeration)
+                }
+                .map_err(|e| DataFusionError::External(Box::new(e)))?)
+            } else {
+                None
+            };
+            self.loaded.insert(ename.to_owned(), variants);
+        }
+
+        Ok(Some((field, self.loaded.get(ename).unwrap())))
+    }
+}
+
+/// Searches `variants` for an element equal to `literal`, returning its
+/// index (the enumeration key) if found. Returns `None` if `variants` is
+/// `None` (the enumeration is not loaded) or `literal` does not match any
+/// current variant.
+pub(crate) fn find_key(variants: &Option<Arc<dyn ArrowArray>>, literal: &ScalarValue) -> Option<usize> {
+    let variants = variants.as_ref()?;
+    (0..variants.len()).find(|&i| {
+        ScalarValue::try_from_array(variants, i)
+            .map(|candidate| &candidate == literal)
+            .unwrap_or(false)
+    })
+}
+
+/// Casts the enumeration key `key` to `field`'s storage datatype and wraps
+/// it as a [ScalarValue].
+fn key_scalar(field: &Field, key: usize) -> Result<ScalarValue, DataFusionError> {
+    use arrow::datatypes::DataType as ArrowDataType;
+
+    let storage_type = tiledb_arrow::schema::arrow_datatype(field.datatype(), field.cell_val_num())
+        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+    Ok(match storage_type {
+        ArrowDataType::Int8 => ScalarValue::Int8(Some(key as i8)),
+        ArrowDataType::Int16 => ScalarValue::Int16(Some(key as i16)),
+        ArrowDataType::Int32 => ScalarValue::Int32(Some(key as i32)),
+        ArrowDataType::Int64 => ScalarValue::Int64(Some(key as i64)),
+        ArrowDataType::UInt8 => ScalarValue::UInt8(Some(key as u8)),
+        ArrowDataType::UInt16 => ScalarValue::UInt16(Some(key as u16)),
+        ArrowDataType::UInt32 => ScalarValue::UInt32(Some(key as u32)),
+        ArrowDataType::UInt64 => ScalarValue::UInt64(Some(key as u64)),
+        other => {
+            return Err(DataFusionError::Internal(format!(
+                "Enumeration key type is not an integer type: {other}"
+            )));
+        }
+    })
+}
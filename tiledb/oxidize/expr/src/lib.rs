@@ -1,5 +1,5 @@
 #[cxx::bridge]
-mod ffi {
+pub(crate) mod ffi {
     #[namespace = "tiledb::sm"]
     extern "C++" {
         include!("tiledb/sm/array_schema/array_schema.h");
@@ -8,6 +8,34 @@ mod ffi {
         type ArraySchema = tiledb_cxx_interface::sm::array_schema::ArraySchema;
         type ASTNode = tiledb_cxx_interface::sm::query::ast::ASTNode;
         type Datatype = tiledb_cxx_interface::sm::enums::Datatype;
+        type QueryConditionOp = tiledb_cxx_interface::sm::enums::QueryConditionOp;
+        type QueryConditionCombinationOp =
+            tiledb_cxx_interface::sm::enums::QueryConditionCombinationOp;
+    }
+
+    #[namespace = "tiledb::oxidize::query::ast"]
+    unsafe extern "C++" {
+        include!("tiledb/sm/query/ast/query_ast.h");
+
+        /// Builds a leaf node testing `field_name` against a single value
+        /// (or, for `IN`/`NOT_IN`, a concatenation of values delimited by
+        /// `offsets`). This is the inverse of `ASTNode::get_data`/`get_offsets`.
+        fn new_value_node(
+            field_name: &str,
+            data: &[u8],
+            offsets: &[u8],
+            op: QueryConditionOp,
+        ) -> UniquePtr<ASTNode>;
+
+        /// Builds a combination node joining `left` and `right` with `op`.
+        fn new_combination_node(
+            left: UniquePtr<ASTNode>,
+            right: UniquePtr<ASTNode>,
+            op: QueryConditionCombinationOp,
+        ) -> UniquePtr<ASTNode>;
+
+        /// Builds the logical negation of `child`.
+        fn new_negation_node(child: UniquePtr<ASTNode>) -> UniquePtr<ASTNode>;
     }
 
     extern "C++" {
@@ -60,8 +88,12 @@ mod ffi {
     }
 }
 
+pub mod enumeration;
 mod logical_expr;
 mod physical_expr;
+pub mod pruning;
+pub mod query_condition;
 
-pub use logical_expr::{LogicalExpr, to_datafusion as query_condition_to_logical_expr};
+pub use logical_expr::LogicalExpr;
 pub use physical_expr::{PhysicalExpr, PhysicalExprOutput, create_physical_expr};
+pub use query_condition::to_datafusion as query_condition_to_logical_expr;
@@ -31,13 +31,18 @@ impl LogicalExpr {
 
     pub fn output_type(&self, schema: &ArraySchema) -> Result<ArrowDataType, TypeError> {
         let cols = self.0.column_refs();
-        let arrow_schema = tiledb_arrow::schema::project_arrow(schema, WhichSchema::View, |f| {
-            let Ok(field_name) = f.name() else {
-                // NB: if the field name is not UTF-8 then it cannot possibly match the column name
-                return false;
-            };
-            cols.contains(&Column::new_unqualified(field_name))
-        })?;
+        let arrow_schema = tiledb_arrow::schema::project_arrow(
+            schema,
+            WhichSchema::View,
+            &tiledb_arrow::schema::EnumerationTypeCache::default(),
+            |f| {
+                let Ok(field_name) = f.name() else {
+                    // NB: if the field name is not UTF-8 then it cannot possibly match the column name
+                    return false;
+                };
+                cols.contains(&Column::new_unqualified(field_name))
+            },
+        )?;
         let dfschema = {
             // SAFETY: the only error we can get from the above is if the arrow schema
             // has duplicate names, which will not happen since it was constructed from
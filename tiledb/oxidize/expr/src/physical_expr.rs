@@ -35,6 +35,8 @@ pub enum PhysicalExprOutputError {
     Cast(#[source] DataFusionError),
     #[error("Cannot read array as static datatype '{0}': found '{1}'")]
     InvalidStaticType(&'static str, ArrowDataType),
+    #[error("Filtering record batch by selection: {0}")]
+    Filter(#[source] datafusion::common::arrow::error::ArrowError),
 }
 
 /// Wraps a DataFusion [PhysicalExpr] for passing across the FFI boundary.
@@ -94,9 +96,13 @@ impl PhysicalExprOutput {
                     .map_err(PhysicalExprOutputError::Cast)?,
             ),
             ColumnarValue::Array(a) => {
-                ColumnarValue::Array(compute::kernels::cast::cast(a, &arrow_type).map_err(|e| {
-                    PhysicalExprOutputError::Cast(DataFusionError::ArrowError(e, None))
-                })?)
+                // Tolerates nested `LargeList`/`FixedSizeList` results whose
+                // child field is merely named differently than `arrow_type`'s
+                // (e.g. `item` vs `element`); see the function docs.
+                let renamed = tiledb_arrow::schema::rename_nested_fields(Arc::clone(a), &arrow_type);
+                ColumnarValue::Array(compute::kernels::cast::cast(&renamed, &arrow_type).map_err(
+                    |e| PhysicalExprOutputError::Cast(DataFusionError::ArrowError(e, None)),
+                )?)
             }
         };
         Ok(Box::new(PhysicalExprOutput(columnar_value)))
@@ -153,4 +159,62 @@ impl PhysicalExprOutput {
             }
         }
     }
+
+    /// Returns `self` as a row mask of `num_rows` cells, treating a `null`
+    /// the same as `false` (`WHERE` semantics). `self` must be of Arrow
+    /// `Boolean` type, e.g. a compiled `QueryConditionExpr` predicate's
+    /// evaluation result; `num_rows` is only needed to expand the
+    /// scalar-boolean case (the whole batch matched, or none of it did)
+    /// into a concrete mask.
+    fn as_boolean_mask(&self, num_rows: usize) -> Result<aa::BooleanArray, PhysicalExprOutputError> {
+        match &self.0 {
+            ColumnarValue::Scalar(ScalarValue::Boolean(selected)) => {
+                Ok(aa::BooleanArray::from(vec![
+                    selected.unwrap_or(false);
+                    num_rows
+                ]))
+            }
+            ColumnarValue::Scalar(s) => Err(PhysicalExprOutputError::InvalidStaticType(
+                "selection (boolean)",
+                s.data_type().clone(),
+            )),
+            ColumnarValue::Array(a) => {
+                if *a.data_type() == adt::DataType::Boolean {
+                    // SAFETY: type check right above this
+                    Ok(a.as_any()
+                        .downcast_ref::<aa::BooleanArray>()
+                        .unwrap()
+                        .clone())
+                } else {
+                    Err(PhysicalExprOutputError::InvalidStaticType(
+                        "selection (boolean)",
+                        a.data_type().clone(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Returns the 0-based indices of the `num_rows` cells where `self`
+    /// evaluates to `true`. See [Self::as_boolean_mask] for how `self` is
+    /// interpreted as a row mask.
+    pub fn as_selection(&self, num_rows: usize) -> Result<Vec<u64>, PhysicalExprOutputError> {
+        let mask = self.as_boolean_mask(num_rows)?;
+        Ok((0..mask.len() as u64)
+            .filter(|&i| mask.is_valid(i as usize) && mask.value(i as usize))
+            .collect())
+    }
+
+    /// Drops every row of `records` for which `self` does not evaluate to
+    /// `true`. See [Self::as_boolean_mask] for how `self` is interpreted as
+    /// a row mask.
+    pub fn apply_filter(
+        &self,
+        records: &ArrowRecordBatch,
+    ) -> Result<Box<ArrowRecordBatch>, PhysicalExprOutputError> {
+        let mask = self.as_boolean_mask(records.arrow.num_rows())?;
+        let arrow = compute::filter_record_batch(&records.arrow, &mask)
+            .map_err(PhysicalExprOutputError::Filter)?;
+        Ok(Box::new(ArrowRecordBatch { arrow }))
+    }
 }
@@ -0,0 +1,217 @@
+//! Evaluates whether a tile can be skipped for a query condition without
+//! reading it, analogous to DataFusion's `PruningPredicate`: each leaf of the
+//! condition tree is evaluated against a column's `[min, max]` summary rather
+//! than its actual values, producing a conservative "may contain a match"
+//! decision.
+//!
+//! Predicates are expected to already be in "storage" typing (see
+//! `tiledb_arrow::schema`'s module docs, and [crate::enumeration]): for an
+//! enumerated column this means the comparisons are against the enumeration's
+//! integer keys, so pruning is done on the key interval rather than the
+//! value, matching what per-tile statistics are actually computed over.
+
+use datafusion::common::ScalarValue;
+use datafusion::logical_expr::expr::InList;
+use datafusion::logical_expr::{BinaryExpr, Expr, Operator};
+
+/// The `[min, max]` summary of one column's values in a tile.
+#[derive(Debug, Clone)]
+pub struct ColumnStatistics {
+    pub min: ScalarValue,
+    pub max: ScalarValue,
+    /// Number of null cells of this column in the tile. `None` means this is
+    /// not tracked, in which case `IS NULL`/`IS NOT NULL` predicates
+    /// conservatively keep the tile.
+    pub null_count: Option<u64>,
+}
+
+/// Supplies per-tile [ColumnStatistics] by column name. Columns with no
+/// entry are treated as unknown, and predicates referencing them
+/// conservatively keep the tile.
+pub trait PruningStatistics {
+    fn column_statistics(&self, name: &str) -> Option<ColumnStatistics>;
+
+    /// The number of cells (rows) in the tile, if known. Used to reason
+    /// about `IS NOT NULL`, which only becomes unsatisfiable when every
+    /// cell in the tile is null. Defaults to `None` (unknown).
+    fn row_count(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// A [PruningStatistics] implementation backed by a plain map of
+/// pre-computed per-column statistics, e.g. the min/max/null-count metadata
+/// a fragment already tracks per tile. Callers build one of these per tile
+/// to drive [may_match] (and a tile-skip fast path built on top of it)
+/// without writing their own [PruningStatistics] impl.
+#[derive(Debug, Clone, Default)]
+pub struct TileStatistics {
+    pub row_count: Option<u64>,
+    pub columns: std::collections::HashMap<String, ColumnStatistics>,
+}
+
+impl PruningStatistics for TileStatistics {
+    fn column_statistics(&self, name: &str) -> Option<ColumnStatistics> {
+        self.columns.get(name).cloned()
+    }
+
+    fn row_count(&self) -> Option<u64> {
+        self.row_count
+    }
+}
+
+/// Returns whether a tile summarized by `stats` could possibly satisfy
+/// `expr`. Returning `false` means the tile is guaranteed not to contain a
+/// matching row and can be skipped; returning `true` does not mean it does
+/// contain one, only that pruning could not rule it out. Sub-expressions
+/// this walk does not recognize (including negation, since an "unknown"
+/// interval result cannot be soundly inverted) conservatively return `true`.
+pub fn may_match(expr: &Expr, stats: &dyn PruningStatistics) -> bool {
+    match expr {
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::And,
+            right,
+        }) => may_match(left, stats) && may_match(right, stats),
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::Or,
+            right,
+        }) => may_match(left, stats) || may_match(right, stats),
+        Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
+            may_match_comparison(left, *op, right, stats)
+        }
+        Expr::InList(InList {
+            expr,
+            list,
+            negated,
+        }) => may_match_in_list(expr, list, *negated, stats),
+        Expr::IsNull(column) => may_match_null_test(column, true, stats),
+        Expr::IsNotNull(column) => may_match_null_test(column, false, stats),
+        _ => true,
+    }
+}
+
+/// `expect_null == true` checks `col IS NULL`, `false` checks `col IS NOT
+/// NULL`. The former is unsatisfiable when the tile has no null cells; the
+/// latter only when every cell in the tile is null.
+fn may_match_null_test(column: &Expr, expect_null: bool, stats: &dyn PruningStatistics) -> bool {
+    let Expr::Column(column) = column else {
+        return true;
+    };
+    let Some(ColumnStatistics { null_count, .. }) = stats.column_statistics(&column.name) else {
+        return true;
+    };
+    let Some(null_count) = null_count else {
+        return true;
+    };
+
+    if expect_null {
+        null_count > 0
+    } else {
+        match stats.row_count() {
+            Some(row_count) => null_count < row_count,
+            None => true,
+        }
+    }
+}
+
+/// Reverses a comparison operator so that `literal OP column` can be
+/// evaluated as `column REV(OP) literal`. `Eq`/`NotEq` are symmetric.
+fn reverse_comparison(op: Operator) -> Operator {
+    match op {
+        Operator::Lt => Operator::Gt,
+        Operator::LtEq => Operator::GtEq,
+        Operator::Gt => Operator::Lt,
+        Operator::GtEq => Operator::LtEq,
+        other => other,
+    }
+}
+
+fn may_match_comparison(
+    left: &Expr,
+    op: Operator,
+    right: &Expr,
+    stats: &dyn PruningStatistics,
+) -> bool {
+    let (column, literal, op) = match (left, right) {
+        (Expr::Column(c), Expr::Literal(v)) => (c, v, op),
+        (Expr::Literal(v), Expr::Column(c)) => (c, v, reverse_comparison(op)),
+        _ => return true,
+    };
+
+    let Some(ColumnStatistics { min, max, .. }) = stats.column_statistics(&column.name) else {
+        return true;
+    };
+
+    use std::cmp::Ordering;
+    match op {
+        Operator::Eq => in_range(literal, &min, &max),
+        Operator::NotEq => {
+            // Only prunable when the column is constant within the tile and
+            // equal to the literal, i.e. every cell equals it.
+            !(min.partial_cmp(&max) == Some(Ordering::Equal)
+                && min.partial_cmp(literal) == Some(Ordering::Equal))
+        }
+        // `col < v` is unsatisfiable when `min >= v`.
+        Operator::Lt => !matches!(
+            min.partial_cmp(literal),
+            Some(Ordering::Greater | Ordering::Equal)
+        ),
+        // `col <= v` is unsatisfiable when `min > v`.
+        Operator::LtEq => !matches!(min.partial_cmp(literal), Some(Ordering::Greater)),
+        // `col > v` is unsatisfiable when `max <= v`.
+        Operator::Gt => !matches!(
+            max.partial_cmp(literal),
+            Some(Ordering::Less | Ordering::Equal)
+        ),
+        // `col >= v` is unsatisfiable when `max < v`.
+        Operator::GtEq => !matches!(max.partial_cmp(literal), Some(Ordering::Less)),
+        _ => true,
+    }
+}
+
+fn may_match_in_list(
+    expr: &Expr,
+    list: &[Expr],
+    negated: bool,
+    stats: &dyn PruningStatistics,
+) -> bool {
+    let Expr::Column(column) = expr else {
+        return true;
+    };
+    let Some(ColumnStatistics { min, max, .. }) = stats.column_statistics(&column.name) else {
+        return true;
+    };
+    let Some(literals) = list
+        .iter()
+        .map(|e| match e {
+            Expr::Literal(v) => Some(v),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()
+    else {
+        // a non-literal member; we cannot reason about the set at all
+        return true;
+    };
+
+    if negated {
+        // Only prunable when the column is constant within the tile and the
+        // set excludes that one value, i.e. every cell is excluded.
+        !(min.partial_cmp(&max) == Some(std::cmp::Ordering::Equal)
+            && literals
+                .iter()
+                .any(|v| min.partial_cmp(v) == Some(std::cmp::Ordering::Equal)))
+    } else {
+        literals.iter().any(|v| in_range(v, &min, &max))
+    }
+}
+
+/// Returns whether `v` could fall within `[min, max]`. Incomparable values
+/// (e.g. differing types) conservatively count as "in range".
+fn in_range(v: &ScalarValue, min: &ScalarValue, max: &ScalarValue) -> bool {
+    let below_min = matches!(v.partial_cmp(min), Some(std::cmp::Ordering::Less));
+    let above_max = matches!(v.partial_cmp(max), Some(std::cmp::Ordering::Greater));
+    !(below_min || above_max)
+}
+
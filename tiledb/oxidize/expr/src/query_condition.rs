@@ -10,12 +10,12 @@ use datafusion::common::arrow::array::{
 use datafusion::common::arrow::buffer::OffsetBuffer;
 use datafusion::common::arrow::datatypes::Field as ArrowField;
 use datafusion::common::{Column, ScalarValue};
-use datafusion::logical_expr::expr::InList;
+use datafusion::logical_expr::expr::{Between, InList};
 use datafusion::logical_expr::{BinaryExpr, Expr, Operator};
 use itertools::Itertools;
 use num_traits::FromBytes;
 use tiledb_arrow::offsets::Error as OffsetsError;
-use tiledb_cxx_interface::sm::array_schema::{ArraySchema, CellValNum, Field};
+use tiledb_cxx_interface::sm::array_schema::{ArraySchema, CellValNum, Enumeration, Field};
 use tiledb_cxx_interface::sm::enums::{Datatype, QueryConditionCombinationOp, QueryConditionOp};
 use tiledb_cxx_interface::sm::misc::ByteVecValue;
 use tiledb_cxx_interface::sm::query::ast::ASTNode;
@@ -47,6 +47,8 @@ pub enum InternalError {
     NotTree(usize),
     #[error("Error in field '{0}': {1}")]
     SchemaField(String, tiledb_arrow::schema::FieldError),
+    #[error("Error materializing enumeration variants: {0}")]
+    Enumeration(tiledb_arrow::enumeration::Error),
 }
 
 /// An error resulting from an invalid query condition syntax tree for a schema.
@@ -56,8 +58,12 @@ pub enum InternalError {
 pub enum UserError {
     #[error("Unknown dimension or attribute: {0}")]
     UnknownField(String),
+    #[error("Unknown enumeration: {0}")]
+    UnknownEnumeration(String),
     #[error("Field name is not UTF-8: {0}")]
     FieldNameNotUtf8(Utf8Error),
+    #[error("Field value is not UTF-8: {0}")]
+    StringNotUtf8(Utf8Error),
     #[error("Value cannot be coerced to datatype '{0}': invalid value size '{1}'")]
     DatatypeMismatch(Datatype, usize),
     #[error("Cell val num mismatch: expected {0}, found {1}")]
@@ -68,6 +74,61 @@ pub enum UserError {
     InListCellValNumMismatch(CellValNum, usize),
     #[error("Variable-length data offsets: ")]
     InListVarOffsets(#[from] OffsetsError),
+    #[error("Expression cannot be pushed down to a query condition: {0}")]
+    UnsupportedExpr(Expr),
+}
+
+/// Converts a value decoded under a field's physical type into the
+/// [ScalarValue] matching its logical `Datatype`, e.g. a `DATETIME_MS`
+/// field's `i64` becomes `ScalarValue::TimestampMillisecond` rather than a
+/// bare `Int64`, and a `BOOL` field's `u8` becomes `ScalarValue::Boolean`.
+/// This mirrors the semantic types `tiledb_arrow::schema::arrow_primitive_datatype`
+/// assigns those fields in the Arrow schema, so the resulting `Expr`
+/// type-checks against it without DataFusion inserting a cast.
+trait IntoLogicalScalar: Sized {
+    fn into_logical_scalar(self, datatype: Datatype) -> ScalarValue;
+}
+
+macro_rules! impl_into_logical_scalar_passthrough {
+    ($($t:ty),*) => {
+        $(
+            impl IntoLogicalScalar for $t {
+                fn into_logical_scalar(self, _datatype: Datatype) -> ScalarValue {
+                    ScalarValue::from(self)
+                }
+            }
+        )*
+    };
+}
+impl_into_logical_scalar_passthrough!(i8, i16, i32, u16, u32, u64, f32, f64);
+
+impl IntoLogicalScalar for i64 {
+    fn into_logical_scalar(self, datatype: Datatype) -> ScalarValue {
+        match datatype {
+            Datatype::DATETIME_SEC => ScalarValue::TimestampSecond(Some(self), None),
+            Datatype::DATETIME_MS => ScalarValue::TimestampMillisecond(Some(self), None),
+            Datatype::DATETIME_US => ScalarValue::TimestampMicrosecond(Some(self), None),
+            Datatype::DATETIME_NS => ScalarValue::TimestampNanosecond(Some(self), None),
+            // `TileDB` decodes all `TIME_*` variants as `i64` (see
+            // `apply_physical_type!`), but Arrow's `Time32` is `i32`-backed;
+            // narrow here rather than in the byte decode so that decoding
+            // stays physical-type-only.
+            Datatype::TIME_SEC => ScalarValue::Time32Second(Some(self as i32)),
+            Datatype::TIME_MS => ScalarValue::Time32Millisecond(Some(self as i32)),
+            Datatype::TIME_US => ScalarValue::Time64Microsecond(Some(self)),
+            Datatype::TIME_NS => ScalarValue::Time64Nanosecond(Some(self)),
+            _ => ScalarValue::from(self),
+        }
+    }
+}
+
+impl IntoLogicalScalar for u8 {
+    fn into_logical_scalar(self, datatype: Datatype) -> ScalarValue {
+        match datatype {
+            Datatype::BOOL => ScalarValue::Boolean(Some(self != 0)),
+            _ => ScalarValue::from(self),
+        }
+    }
 }
 
 /// Returns an iterator over the values of type [T] contained in `bytes`.
@@ -102,6 +163,253 @@ where
     }))
 }
 
+/// Returns whether `datatype`'s `CellValNum::Var` representation is a UTF-8
+/// string rather than a list of physical-type values.
+fn is_string_datatype(datatype: Datatype) -> bool {
+    matches!(
+        datatype,
+        Datatype::STRING_ASCII | Datatype::STRING_UTF8 | Datatype::CHAR
+    )
+}
+
+/// Builds a binary comparison against a single string literal for a
+/// `CellValNum::Var` char/string field.
+fn string_binary_expr(field: &Field, ast: &ASTNode, operator: Operator) -> Result<Expr, Error> {
+    let column = Expr::Column(Column::from_name(
+        field.name().map_err(UserError::FieldNameNotUtf8)?,
+    ));
+    let value = std::str::from_utf8(ast.get_data().as_slice())
+        .map_err(UserError::StringNotUtf8)?
+        .to_owned();
+
+    Ok(Expr::BinaryExpr(BinaryExpr {
+        left: Box::new(column),
+        op: operator,
+        right: Box::new(Expr::Literal(ScalarValue::LargeUtf8(Some(value)))),
+    }))
+}
+
+/// Builds an `IN`/`NOT IN` list of string literals for a `CellValNum::Var`
+/// char/string field, slicing `ast.get_data()` according to `ast.get_offsets()`.
+fn string_in_list(field: &Field, ast: &ASTNode, negated: bool) -> Result<Expr, Error> {
+    let column = Expr::Column(Column::from_name(
+        field.name().map_err(UserError::FieldNameNotUtf8)?,
+    ));
+
+    let data = ast.get_data().as_slice();
+    let offsets = tiledb_arrow::offsets::try_from_bytes_and_num_values(
+        1,
+        ast.get_offsets().as_slice(),
+        data.len(),
+    )
+    .map_err(UserError::from)?;
+
+    let in_list = offsets
+        .windows(2)
+        .map(|w| {
+            let value = std::str::from_utf8(&data[w[0] as usize..w[1] as usize])
+                .map_err(UserError::StringNotUtf8)?
+                .to_owned();
+            Ok(Expr::Literal(ScalarValue::LargeUtf8(Some(value))))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(Expr::InList(InList {
+        expr: Box::new(column),
+        list: in_list,
+        negated,
+    }))
+}
+
+/// Decodes a single value from `ast.get_data()` under an enumeration's
+/// value datatype (as opposed to the field's physical key datatype).
+fn decode_enumeration_value(enumeration: &Enumeration, ast: &ASTNode) -> Result<ScalarValue, Error> {
+    let datatype = enumeration.datatype();
+
+    if is_string_datatype(datatype) {
+        let value = std::str::from_utf8(ast.get_data().as_slice())
+            .map_err(UserError::StringNotUtf8)?
+            .to_owned();
+        return Ok(ScalarValue::LargeUtf8(Some(value)));
+    }
+
+    fn apply<T>(datatype: Datatype, bytes: &ByteVecValue) -> Result<ScalarValue, Error>
+    where
+        T: FromBytes + IntoLogicalScalar,
+        <T as FromBytes>::Bytes: for<'a> TryFrom<&'a [u8]>,
+    {
+        let Some(value) = values_iter::<T>(datatype, bytes)?.next() else {
+            return Err(UserError::CellValNumMismatch(CellValNum::Single, 0).into());
+        };
+        Ok(value.into_logical_scalar(datatype))
+    }
+
+    apply_physical_type!(
+        datatype,
+        NativeType,
+        apply::<NativeType>(datatype, ast.get_data()),
+        |invalid: Datatype| Err(InternalError::InvalidDatatype(invalid.repr.into()).into())
+    )
+}
+
+/// Decodes the `IN`/`NOT IN` members of `ast` under an enumeration's value
+/// datatype, the list-valued counterpart of [decode_enumeration_value].
+fn decode_enumeration_values(
+    enumeration: &Enumeration,
+    ast: &ASTNode,
+) -> Result<Vec<ScalarValue>, Error> {
+    let datatype = enumeration.datatype();
+
+    if is_string_datatype(datatype) {
+        let data = ast.get_data().as_slice();
+        let offsets = tiledb_arrow::offsets::try_from_bytes_and_num_values(
+            1,
+            ast.get_offsets().as_slice(),
+            data.len(),
+        )
+        .map_err(UserError::from)?;
+
+        return offsets
+            .windows(2)
+            .map(|w| {
+                let value = std::str::from_utf8(&data[w[0] as usize..w[1] as usize])
+                    .map_err(UserError::StringNotUtf8)?
+                    .to_owned();
+                Ok(ScalarValue::LargeUtf8(Some(value)))
+            })
+            .collect();
+    }
+
+    fn apply<T>(datatype: Datatype, bytes: &ByteVecValue) -> Result<Vec<ScalarValue>, Error>
+    where
+        T: FromBytes + IntoLogicalScalar,
+        <T as FromBytes>::Bytes: for<'a> TryFrom<&'a [u8]>,
+    {
+        Ok(values_iter::<T>(datatype, bytes)?
+            .map(|v| v.into_logical_scalar(datatype))
+            .collect())
+    }
+
+    apply_physical_type!(
+        datatype,
+        NativeType,
+        apply::<NativeType>(datatype, ast.get_data()),
+        |invalid: Datatype| Err(InternalError::InvalidDatatype(invalid.repr.into()).into())
+    )
+}
+
+/// Builds a binary comparison against an enumerated field, resolving the
+/// condition's value through `field`'s enumeration so the literal carries
+/// the [ScalarValue::Dictionary] shape that lines up with the
+/// dictionary-encoded column DataFusion sees, rather than a bare key index.
+/// A value which is not a current variant of the enumeration resolves to a
+/// constant (`=` becomes `false`, `!=` becomes `true`) instead of an error,
+/// mirroring [crate::enumeration::rewrite_view_to_storage]'s treatment of
+/// the same case.
+fn enum_binary_expr(
+    schema: &ArraySchema,
+    field: &Field,
+    ast: &ASTNode,
+    op: Operator,
+) -> Result<Expr, Error> {
+    let column = Expr::Column(Column::from_name(
+        field.name().map_err(UserError::FieldNameNotUtf8)?,
+    ));
+
+    let ename = field
+        .enumeration_name()
+        .expect("caller checked field.enumeration_name().is_some()")
+        .map_err(UserError::FieldNameNotUtf8)?;
+    if !schema.has_enumeration(ename) {
+        return Err(UserError::UnknownEnumeration(ename.to_owned()).into());
+    }
+    let enumeration = schema.enumeration(ename);
+    let variants = unsafe {
+        // SAFETY: consumed below, before `enumeration` goes out of scope
+        tiledb_arrow::enumeration::array_from_enumeration(&enumeration)
+    }
+    .map_err(InternalError::Enumeration)?;
+
+    let value = decode_enumeration_value(&enumeration, ast)?;
+    if crate::enumeration::find_key(&Some(variants), &value).is_none() {
+        return Ok(Expr::Literal(ScalarValue::Boolean(Some(
+            op == Operator::NotEq,
+        ))));
+    }
+
+    let key_type = tiledb_arrow::schema::arrow_primitive_datatype(field.datatype()).map_err(|e| {
+        InternalError::SchemaField(field.name_cxx().to_string_lossy().into_owned(), e)
+    })?;
+
+    Ok(Expr::BinaryExpr(BinaryExpr {
+        left: Box::new(column),
+        op,
+        right: Box::new(Expr::Literal(ScalarValue::Dictionary(
+            Box::new(key_type),
+            Box::new(value),
+        ))),
+    }))
+}
+
+/// Builds an `IN`/`NOT IN` list against an enumerated field, the list-valued
+/// counterpart of [enum_binary_expr]. Members which are not current variants
+/// of the enumeration are dropped from the list rather than erroring; if
+/// none remain, the whole list resolves to a constant the same way
+/// [crate::enumeration::rewrite_view_to_storage] handles an empty match set.
+fn enum_in_list(
+    schema: &ArraySchema,
+    field: &Field,
+    ast: &ASTNode,
+    negated: bool,
+) -> Result<Expr, Error> {
+    let column = Expr::Column(Column::from_name(
+        field.name().map_err(UserError::FieldNameNotUtf8)?,
+    ));
+
+    let ename = field
+        .enumeration_name()
+        .expect("caller checked field.enumeration_name().is_some()")
+        .map_err(UserError::FieldNameNotUtf8)?;
+    if !schema.has_enumeration(ename) {
+        return Err(UserError::UnknownEnumeration(ename.to_owned()).into());
+    }
+    let enumeration = schema.enumeration(ename);
+    let variants = Some(
+        unsafe {
+            // SAFETY: consumed below, before `enumeration` goes out of scope
+            tiledb_arrow::enumeration::array_from_enumeration(&enumeration)
+        }
+        .map_err(InternalError::Enumeration)?,
+    );
+
+    let key_type = tiledb_arrow::schema::arrow_primitive_datatype(field.datatype()).map_err(|e| {
+        InternalError::SchemaField(field.name_cxx().to_string_lossy().into_owned(), e)
+    })?;
+
+    let in_list = decode_enumeration_values(&enumeration, ast)?
+        .into_iter()
+        .filter(|value| crate::enumeration::find_key(&variants, value).is_some())
+        .map(|value| {
+            Expr::Literal(ScalarValue::Dictionary(
+                Box::new(key_type.clone()),
+                Box::new(value),
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    if in_list.is_empty() {
+        // none of the members are current variants of the enumeration:
+        // `IN ()` is always false, `NOT IN ()` is always true
+        return Ok(Expr::Literal(ScalarValue::Boolean(Some(negated))));
+    }
+
+    Ok(Expr::InList(InList {
+        expr: Box::new(column),
+        list: in_list,
+        negated,
+    }))
+}
+
 fn leaf_ast_to_binary_expr(
     schema: &ArraySchema,
     ast: &ASTNode,
@@ -113,23 +421,39 @@ fn leaf_ast_to_binary_expr(
         );
     };
 
-    fn apply<T>(field: &Field, ast: &ASTNode, operator: Operator) -> Result<Expr, Error>
+    if is_string_datatype(field.datatype()) && field.cell_val_num() == CellValNum::Var {
+        return string_binary_expr(&field, ast, op);
+    }
+
+    if matches!(op, Operator::Eq | Operator::NotEq) && field.enumeration_name().is_some() {
+        return enum_binary_expr(schema, &field, ast, op);
+    }
+
+    fn apply<T>(
+        array_schema: &ArraySchema,
+        field: &Field,
+        ast: &ASTNode,
+        operator: Operator,
+    ) -> Result<Expr, Error>
     where
-        T: FromBytes,
+        T: FromBytes + IntoLogicalScalar,
         <T as FromBytes>::Bytes: for<'a> TryFrom<&'a [u8]>,
-        ScalarValue: From<T> + From<Option<T>>,
     {
         let column = Expr::Column(Column::from_name(
             field.name().map_err(UserError::FieldNameNotUtf8)?,
         ));
 
         let mut values = values_iter::<T>(field.datatype(), ast.get_data())?
-            .map(ScalarValue::from)
+            .map(|v| v.into_logical_scalar(field.datatype()))
             .peekable();
 
-        let expect_datatype = tiledb_arrow::schema::field_arrow_datatype(field).map_err(|e| {
-            InternalError::SchemaField(field.name_cxx().to_string_lossy().into_owned(), e)
-        })?;
+        let expect_datatype = tiledb_arrow::schema::field_arrow_datatype(
+            array_schema,
+            tiledb_arrow::schema::WhichSchema::View,
+            field,
+            &tiledb_arrow::schema::EnumerationTypeCache::default(),
+        )
+        .map_err(|e| InternalError::SchemaField(field.name_cxx().to_string_lossy().into_owned(), e))?;
 
         let right = match field.cell_val_num() {
             CellValNum::Single => {
@@ -199,7 +523,7 @@ fn leaf_ast_to_binary_expr(
     apply_physical_type!(
         value_type,
         NativeType,
-        apply::<NativeType>(&field, ast, op),
+        apply::<NativeType>(schema, &field, ast, op),
         |invalid: Datatype| Err(InternalError::InvalidDatatype(invalid.repr.into()).into())
     )
 }
@@ -211,11 +535,18 @@ fn leaf_ast_to_in_list(schema: &ArraySchema, ast: &ASTNode, negated: bool) -> Re
         );
     };
 
+    if is_string_datatype(field.datatype()) && field.cell_val_num() == CellValNum::Var {
+        return string_in_list(&field, ast, negated);
+    }
+
+    if field.enumeration_name().is_some() {
+        return enum_in_list(schema, &field, ast, negated);
+    }
+
     fn apply<T>(field: &Field, ast: &ASTNode, negated: bool) -> Result<Expr, Error>
     where
-        T: FromBytes,
+        T: FromBytes + IntoLogicalScalar,
         <T as FromBytes>::Bytes: for<'a> TryFrom<&'a [u8]>,
-        ScalarValue: From<T> + From<Option<T>>,
     {
         let column = Expr::Column(Column::from_name(
             field.name().map_err(UserError::FieldNameNotUtf8)?,
@@ -225,18 +556,28 @@ fn leaf_ast_to_in_list(schema: &ArraySchema, ast: &ASTNode, negated: bool) -> Re
 
         let in_list = match field.cell_val_num() {
             CellValNum::Single => scalars
-                .map(ScalarValue::from)
+                .map(|v| v.into_logical_scalar(field.datatype()))
                 .map(Expr::Literal)
                 .collect::<Vec<_>>(),
             CellValNum::Fixed(nz) => {
                 let fixed_size = nz.get() as usize;
                 let array_values = if scalars.peek().is_none() {
-                    let value_data_type = ScalarValue::from(scalars.next()).data_type();
+                    let value_data_type =
+                        tiledb_arrow::schema::arrow_primitive_datatype(field.datatype())
+                            .map_err(|e| {
+                                InternalError::SchemaField(
+                                    field.name_cxx().to_string_lossy().into_owned(),
+                                    e,
+                                )
+                            })?;
                     aa::make_array(ArrayData::new_empty(&value_data_type))
                 } else {
                     // SAFETY: `values_iter` produces all the same native type
                     // `scalars` is also non-empty per `peek`
-                    ScalarValue::iter_to_array(scalars.map(ScalarValue::from)).unwrap()
+                    ScalarValue::iter_to_array(
+                        scalars.map(|v| v.into_logical_scalar(field.datatype())),
+                    )
+                    .unwrap()
                 };
                 if array_values.len() % fixed_size != 0 {
                     return Err(UserError::InListCellValNumMismatch(
@@ -271,12 +612,22 @@ fn leaf_ast_to_in_list(schema: &ArraySchema, ast: &ASTNode, negated: bool) -> Re
             }
             CellValNum::Var => {
                 let array_values = if scalars.peek().is_none() {
-                    let value_data_type = ScalarValue::from(scalars.next()).data_type();
+                    let value_data_type =
+                        tiledb_arrow::schema::arrow_primitive_datatype(field.datatype())
+                            .map_err(|e| {
+                                InternalError::SchemaField(
+                                    field.name_cxx().to_string_lossy().into_owned(),
+                                    e,
+                                )
+                            })?;
                     aa::make_array(ArrayData::new_empty(&value_data_type))
                 } else {
                     // SAFETY: `values_iter` produces all the same native type
                     // FIXME: what if empty?
-                    ScalarValue::iter_to_array(scalars.map(ScalarValue::from)).unwrap()
+                    ScalarValue::iter_to_array(
+                        scalars.map(|v| v.into_logical_scalar(field.datatype())),
+                    )
+                    .unwrap()
                 };
                 assert!(!array_values.is_nullable());
 
@@ -485,8 +836,378 @@ pub fn to_datafusion(
     schema: &ArraySchema,
     query_condition: &ASTNode,
 ) -> Result<Box<LogicalExpr>, Error> {
-    Ok(Box::new(LogicalExpr(to_datafusion_impl(
+    Ok(Box::new(LogicalExpr(simplify_expr(to_datafusion_impl(
         schema,
         query_condition,
-    )?)))
+    )?))))
+}
+
+/// Returns `Some(b)` if `expr` is the boolean literal `b`.
+fn as_bool_literal(expr: &Expr) -> Option<bool> {
+    match expr {
+        Expr::Literal(ScalarValue::Boolean(Some(b))) => Some(*b),
+        _ => None,
+    }
+}
+
+/// Constant-folds `And`/`Or`/`Not` nodes produced by [to_datafusion_impl],
+/// e.g. `ALWAYS_TRUE` on a non-nullable field becoming a bare `true` literal
+/// that a combination node then carries as a dead operand. This rewrites
+/// bottom-up so that nested constants (`And(And(x, true), false)`) collapse
+/// in a single recursive pass. `IsNotNull`, the guard `ALWAYS_TRUE` expands to
+/// on a nullable field, is left as an opaque leaf since it is not actually
+/// constant.
+fn simplify_expr(expr: Expr) -> Expr {
+    match expr {
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::And,
+            right,
+        }) => {
+            let left = simplify_expr(*left);
+            let right = simplify_expr(*right);
+            match (as_bool_literal(&left), as_bool_literal(&right)) {
+                (Some(true), _) => right,
+                (_, Some(true)) => left,
+                (Some(false), _) | (_, Some(false)) => {
+                    Expr::Literal(ScalarValue::Boolean(Some(false)))
+                }
+                _ => Expr::BinaryExpr(BinaryExpr {
+                    left: Box::new(left),
+                    op: Operator::And,
+                    right: Box::new(right),
+                }),
+            }
+        }
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::Or,
+            right,
+        }) => {
+            let left = simplify_expr(*left);
+            let right = simplify_expr(*right);
+            match (as_bool_literal(&left), as_bool_literal(&right)) {
+                (Some(false), _) => right,
+                (_, Some(false)) => left,
+                (Some(true), _) | (_, Some(true)) => {
+                    Expr::Literal(ScalarValue::Boolean(Some(true)))
+                }
+                _ => Expr::BinaryExpr(BinaryExpr {
+                    left: Box::new(left),
+                    op: Operator::Or,
+                    right: Box::new(right),
+                }),
+            }
+        }
+        Expr::Not(inner) => {
+            let inner = simplify_expr(*inner);
+            match as_bool_literal(&inner) {
+                Some(b) => Expr::Literal(ScalarValue::Boolean(Some(!b))),
+                None => match inner {
+                    Expr::Not(double_negated) => *double_negated,
+                    other => Expr::Not(Box::new(other)),
+                },
+            }
+        }
+        other => other,
+    }
+}
+
+/// Returns the [ASTNode] representing the same predicate as `expr`, the
+/// inverse of [to_datafusion]. This enables predicate pushdown: the caller
+/// can attempt this conversion on a DataFusion filter and, on success, hand
+/// the query condition to the reader instead of evaluating it itself.
+///
+/// Returns [UserError::UnsupportedExpr] (rather than panicking) for any
+/// sub-expression this representation cannot express, e.g. a `LIKE`, an
+/// arithmetic sub-expression, or a literal which does not coerce to the
+/// field's datatype and [CellValNum] -- the caller should fall back to
+/// evaluating that predicate in DataFusion instead of pushing it down.
+pub fn from_datafusion(
+    schema: &ArraySchema,
+    expr: &Expr,
+) -> Result<cxx::UniquePtr<ASTNode>, Error> {
+    from_datafusion_impl(schema, expr)
+}
+
+fn from_datafusion_impl(
+    schema: &ArraySchema,
+    expr: &Expr,
+) -> Result<cxx::UniquePtr<ASTNode>, Error> {
+    match expr {
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::And,
+            right,
+        }) => combination_node(schema, left, right, QueryConditionCombinationOp::AND),
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::Or,
+            right,
+        }) => combination_node(schema, left, right, QueryConditionCombinationOp::OR),
+        Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
+            comparison_node(schema, left, *op, right)
+        }
+        Expr::InList(InList {
+            expr: column,
+            list,
+            negated,
+        }) => in_list_node(schema, column, list, *negated),
+        Expr::Between(Between {
+            expr: value,
+            negated,
+            low,
+            high,
+        }) => between_node(schema, value, *negated, low, high),
+        Expr::IsNull(column) => null_test_node(schema, column, QueryConditionOp::EQ),
+        Expr::IsNotNull(column) => null_test_node(schema, column, QueryConditionOp::NE),
+        Expr::Not(inner) => {
+            let child = from_datafusion_impl(schema, inner)?;
+            Ok(crate::ffi::new_negation_node(child))
+        }
+        other => Err(UserError::UnsupportedExpr(other.clone()).into()),
+    }
+}
+
+/// ANDs together two conditions produced by [from_datafusion], e.g. to
+/// recombine the native conditions of separately-classified conjuncts of a
+/// larger filter.
+pub fn and(
+    lhs: cxx::UniquePtr<ASTNode>,
+    rhs: cxx::UniquePtr<ASTNode>,
+) -> cxx::UniquePtr<ASTNode> {
+    crate::ffi::new_combination_node(lhs, rhs, QueryConditionCombinationOp::AND)
+}
+
+fn combination_node(
+    schema: &ArraySchema,
+    left: &Expr,
+    right: &Expr,
+    op: QueryConditionCombinationOp,
+) -> Result<cxx::UniquePtr<ASTNode>, Error> {
+    let left = from_datafusion_impl(schema, left)?;
+    let right = from_datafusion_impl(schema, right)?;
+    Ok(crate::ffi::new_combination_node(left, right, op))
+}
+
+/// Reverses a comparison operator so that `literal OP column` can be
+/// represented as `column REV(OP) literal`. `Eq`/`NotEq` are symmetric.
+fn reverse_comparison(op: Operator) -> Operator {
+    match op {
+        Operator::Lt => Operator::Gt,
+        Operator::LtEq => Operator::GtEq,
+        Operator::Gt => Operator::Lt,
+        Operator::GtEq => Operator::LtEq,
+        other => other,
+    }
+}
+
+fn query_condition_op(op: Operator) -> Option<QueryConditionOp> {
+    Some(match op {
+        Operator::Lt => QueryConditionOp::LT,
+        Operator::LtEq => QueryConditionOp::LE,
+        Operator::Gt => QueryConditionOp::GT,
+        Operator::GtEq => QueryConditionOp::GE,
+        Operator::Eq => QueryConditionOp::EQ,
+        Operator::NotEq => QueryConditionOp::NE,
+        _ => return None,
+    })
+}
+
+fn comparison_node(
+    schema: &ArraySchema,
+    left: &Expr,
+    op: Operator,
+    right: &Expr,
+) -> Result<cxx::UniquePtr<ASTNode>, Error> {
+    let original = || {
+        Expr::BinaryExpr(BinaryExpr {
+            left: Box::new(left.clone()),
+            op,
+            right: Box::new(right.clone()),
+        })
+    };
+
+    let (column, literal, op) = match (left, right) {
+        (Expr::Column(c), Expr::Literal(v)) => (c, v, op),
+        (Expr::Literal(v), Expr::Column(c)) => (c, v, reverse_comparison(op)),
+        _ => return Err(UserError::UnsupportedExpr(original()).into()),
+    };
+    let Some(qc_op) = query_condition_op(op) else {
+        return Err(UserError::UnsupportedExpr(original()).into());
+    };
+
+    let field = lookup_field(schema, &column.name)?;
+    let data = scalar_to_bytes(&field, literal, &original)?;
+
+    Ok(crate::ffi::new_value_node(&column.name, &data, &[], qc_op))
+}
+
+/// Lowers `value BETWEEN low AND high` (or, negated, `value NOT BETWEEN low
+/// AND high`) to `low <= value AND value <= high`, optionally wrapped in a
+/// negation node.
+fn between_node(
+    schema: &ArraySchema,
+    value: &Expr,
+    negated: bool,
+    low: &Expr,
+    high: &Expr,
+) -> Result<cxx::UniquePtr<ASTNode>, Error> {
+    let lower_bound = comparison_node(schema, low, Operator::LtEq, value)?;
+    let upper_bound = comparison_node(schema, value, Operator::LtEq, high)?;
+    let range = crate::ffi::new_combination_node(
+        lower_bound,
+        upper_bound,
+        QueryConditionCombinationOp::AND,
+    );
+
+    Ok(if negated {
+        crate::ffi::new_negation_node(range)
+    } else {
+        range
+    })
+}
+
+fn null_test_node(
+    schema: &ArraySchema,
+    column: &Expr,
+    op: QueryConditionOp,
+) -> Result<cxx::UniquePtr<ASTNode>, Error> {
+    let Expr::Column(column) = column else {
+        return Err(UserError::UnsupportedExpr(column.clone()).into());
+    };
+    let field = lookup_field(schema, &column.name)?;
+    if !field.nullable() {
+        return Err(UserError::UnsupportedExpr(Expr::Column(column.clone())).into());
+    }
+    Ok(crate::ffi::new_value_node(&column.name, &[], &[], op))
+}
+
+fn in_list_node(
+    schema: &ArraySchema,
+    expr: &Expr,
+    list: &[Expr],
+    negated: bool,
+) -> Result<cxx::UniquePtr<ASTNode>, Error> {
+    let Expr::Column(column) = expr else {
+        return Err(UserError::UnsupportedExpr(expr.clone()).into());
+    };
+    let field = lookup_field(schema, &column.name)?;
+
+    let values = list
+        .iter()
+        .map(|item| match item {
+            Expr::Literal(v) => scalar_to_bytes(&field, v, || item.clone()),
+            other => Err(UserError::UnsupportedExpr(other.clone()).into()),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (data, offsets) = concat_values(&values);
+    let op = if negated {
+        QueryConditionOp::NOT_IN
+    } else {
+        QueryConditionOp::IN
+    };
+
+    Ok(crate::ffi::new_value_node(&column.name, &data, &offsets, op))
+}
+
+/// Returns the schema field named by `name`, or a [UserError::UnknownField]
+/// if there is none.
+fn lookup_field<'a>(schema: &'a ArraySchema, name: &str) -> Result<Field<'a>, Error> {
+    schema
+        .field(name)
+        .ok_or_else(|| UserError::UnknownField(name.to_owned()).into())
+}
+
+/// Serializes `scalar` to TileDB's raw byte representation for `field`'s
+/// datatype, the inverse of [values_iter]/[IntoLogicalScalar]. Only
+/// single-valued fields and UTF-8 strings are supported; anything else
+/// (including literals which do not match the field's type) is reported via
+/// `original` as an unsupported expression.
+fn scalar_to_bytes(
+    field: &Field,
+    scalar: &ScalarValue,
+    original: impl FnOnce() -> Expr,
+) -> Result<Vec<u8>, Error> {
+    if is_string_datatype(field.datatype()) {
+        return match scalar {
+            ScalarValue::Utf8(Some(s)) | ScalarValue::LargeUtf8(Some(s)) => {
+                Ok(s.clone().into_bytes())
+            }
+            _ => Err(UserError::UnsupportedExpr(original()).into()),
+        };
+    }
+
+    if field.cell_val_num() != CellValNum::Single {
+        return Err(UserError::UnsupportedExpr(original()).into());
+    }
+
+    logical_scalar_to_bytes(field.datatype(), scalar)
+        .ok_or_else(|| UserError::UnsupportedExpr(original()).into())
+}
+
+/// The inverse of [IntoLogicalScalar]: recovers a field's raw physical-type
+/// bytes from the typed [ScalarValue] built for its logical `Datatype`.
+fn logical_scalar_to_bytes(datatype: Datatype, scalar: &ScalarValue) -> Option<Vec<u8>> {
+    match (datatype, scalar) {
+        (Datatype::BOOL, ScalarValue::Boolean(Some(b))) => Some(vec![*b as u8]),
+        (Datatype::DATETIME_SEC, ScalarValue::TimestampSecond(Some(v), _)) => {
+            Some(v.to_ne_bytes().to_vec())
+        }
+        (Datatype::DATETIME_MS, ScalarValue::TimestampMillisecond(Some(v), _)) => {
+            Some(v.to_ne_bytes().to_vec())
+        }
+        (Datatype::DATETIME_US, ScalarValue::TimestampMicrosecond(Some(v), _)) => {
+            Some(v.to_ne_bytes().to_vec())
+        }
+        (Datatype::DATETIME_NS, ScalarValue::TimestampNanosecond(Some(v), _)) => {
+            Some(v.to_ne_bytes().to_vec())
+        }
+        (Datatype::TIME_SEC, ScalarValue::Time32Second(Some(v))) => {
+            Some((*v as i64).to_ne_bytes().to_vec())
+        }
+        (Datatype::TIME_MS, ScalarValue::Time32Millisecond(Some(v))) => {
+            Some((*v as i64).to_ne_bytes().to_vec())
+        }
+        (Datatype::TIME_US, ScalarValue::Time64Microsecond(Some(v))) => {
+            Some(v.to_ne_bytes().to_vec())
+        }
+        (Datatype::TIME_NS, ScalarValue::Time64Nanosecond(Some(v))) => {
+            Some(v.to_ne_bytes().to_vec())
+        }
+        (_, scalar) => numeric_scalar_to_bytes(scalar),
+    }
+}
+
+/// Serializes a plain (non-temporal, non-bool) numeric [ScalarValue] to its
+/// native-endian bytes.
+fn numeric_scalar_to_bytes(scalar: &ScalarValue) -> Option<Vec<u8>> {
+    Some(match scalar {
+        ScalarValue::Int8(Some(v)) => v.to_ne_bytes().to_vec(),
+        ScalarValue::Int16(Some(v)) => v.to_ne_bytes().to_vec(),
+        ScalarValue::Int32(Some(v)) => v.to_ne_bytes().to_vec(),
+        ScalarValue::Int64(Some(v)) => v.to_ne_bytes().to_vec(),
+        ScalarValue::UInt8(Some(v)) => v.to_ne_bytes().to_vec(),
+        ScalarValue::UInt16(Some(v)) => v.to_ne_bytes().to_vec(),
+        ScalarValue::UInt32(Some(v)) => v.to_ne_bytes().to_vec(),
+        ScalarValue::UInt64(Some(v)) => v.to_ne_bytes().to_vec(),
+        ScalarValue::Float32(Some(v)) => v.to_ne_bytes().to_vec(),
+        ScalarValue::Float64(Some(v)) => v.to_ne_bytes().to_vec(),
+        _ => return None,
+    })
+}
+
+/// Concatenates `values` into one byte buffer plus the byte offset of each
+/// value (`N` values produce `N` offsets; the final value's length is
+/// implied by the data length), matching the `IN`/`NOT_IN` layout consumed
+/// by `leaf_ast_to_in_list`.
+fn concat_values(values: &[Vec<u8>]) -> (Vec<u8>, Vec<u8>) {
+    let mut data = Vec::new();
+    let mut offsets = Vec::new();
+    for value in values {
+        offsets.extend_from_slice(&(data.len() as i64).to_ne_bytes());
+        data.extend_from_slice(value);
+    }
+    (data, offsets)
 }
@@ -27,6 +27,16 @@ pub enum FieldError {
 }
 
 /// Returns a [DFSchema] which represents the physical field types of `array_schema`.
+///
+/// This file (along with the sibling `record_batch.rs` and `offsets.rs`,
+/// which share its legacy `oxidize::sm::array_schema` types) predates the
+/// `tiledb_cxx_interface`-based rewrite and is no longer declared as a
+/// module in `lib.rs`, so none of it builds or runs anymore. The
+/// `WhichSchema`/enumeration-aware replacement for this function is
+/// `tiledb_arrow::schema::to_arrow`/`project_arrow`, which already lower an
+/// enumerated field to `ArrowDataType::Dictionary` in `WhichSchema::View`
+/// mode; `query_condition::leaf_ast_to_binary_expr` and
+/// `logical_expr::LogicalExpr::output_type` call those instead of this.
 pub fn to_datafusion(array_schema: &ArraySchema) -> Result<DFSchema, Error> {
     let fields = array_schema.fields().map(|f| {
         let field_name = f
@@ -16,7 +16,7 @@ mod ffi {
         #[cxx_name = "new_query_predicates"]
         fn new_query_predicates_ffi(schema: &ArraySchema) -> Result<Box<QueryPredicates>>;
 
-        fn compile(&mut self) -> Result<()>;
+        fn compile(&mut self, schema: &ArraySchema) -> Result<()>;
 
         unsafe fn field_names<'a>(&'a self) -> Vec<&'a str>;
 
@@ -30,18 +30,21 @@ mod ffi {
 
 use std::sync::Arc;
 
-use arrow::datatypes::{DataType, Schema as ArrowSchema};
+use arrow::datatypes::{DataType, Field as ArrowField, Schema as ArrowSchema};
 use datafusion::common::tree_node::TreeNode;
 use datafusion::common::{DFSchema, ScalarValue};
 use datafusion::execution::context::ExecutionProps;
 use datafusion::execution::context::SessionContext;
 use datafusion::execution::session_state::SessionStateBuilder;
-use datafusion::logical_expr::{Expr, ExprSchemable};
+use datafusion::logical_expr::simplify::SimplifyContext;
+use datafusion::logical_expr::{BinaryExpr, Expr, ExprSchemable, Operator};
+use datafusion::optimizer::simplify_expressions::ExprSimplifier;
 use datafusion::physical_plan::{ColumnarValue, PhysicalExpr};
 use itertools::Itertools;
 use num_traits::Zero;
 use tiledb_arrow::schema::WhichSchema;
 use tiledb_cxx_interface::sm::array_schema::ArraySchema;
+use tiledb_cxx_interface::sm::query::ast::ASTNode;
 use tiledb_cxx_interface::sm::query::readers::ResultTile;
 
 #[derive(Debug, thiserror::Error)]
@@ -72,6 +75,14 @@ pub enum CompileError {
     InvalidState,
     #[error("Expression compile error: {0}")]
     PhysicalExpr(#[source] datafusion::common::DataFusionError),
+    #[error("Expression simplification error: {0}")]
+    Simplify(#[source] datafusion::common::DataFusionError),
+    #[error("Enumeration rewrite error: {0}")]
+    Enumeration(#[from] tiledb_expr::enumeration::Error),
+    #[error("Field error: {0}")]
+    Field(#[from] tiledb_arrow::schema::FieldError),
+    #[error("Query condition lowering error: {0}")]
+    QueryCondition(#[source] tiledb_expr::query_condition::Error),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -82,6 +93,14 @@ pub enum EvaluatePredicateError {
     ResultTile(#[from] tiledb_arrow::record_batch::Error),
     #[error("Evaluation error: {0}")]
     Evaluate(#[source] datafusion::common::DataFusionError),
+    #[error("Error gathering selected rows: {0}")]
+    Gather(#[source] arrow::error::ArrowError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RegisterUdfError {
+    #[error("Query is in progress")]
+    InvalidState,
 }
 
 /// Holds state to parse, analyze and evaluate predicates of a TileDB query.
@@ -108,11 +127,37 @@ impl QueryPredicates {
         }
     }
 
+    /// Registers a user-defined scalar function usable by name in predicate
+    /// text subsequently parsed by [Self::add_text_predicate], e.g. a
+    /// `regexp_like` or domain-specific distance function. Column references
+    /// in a UDF's arguments are picked up by [Self::field_names] the same as
+    /// any other expression, since both that and aggregate-function
+    /// detection walk the whole expression tree rather than special-casing
+    /// particular node shapes.
+    ///
+    /// This is only valid from the `Build` state.
+    pub fn register_udf(&mut self, udf: datafusion::logical_expr::ScalarUDF) -> Result<(), RegisterUdfError> {
+        match self {
+            Self::Build(builder) => {
+                builder.register_udf(udf);
+                Ok(())
+            }
+            Self::Evaluate(_) => Err(RegisterUdfError::InvalidState),
+        }
+    }
+
     /// Transitions state from `Build` to `Evaluate`.
-    pub fn compile(&mut self) -> Result<(), CompileError> {
+    ///
+    /// `schema` must be the same schema this was constructed from; it is
+    /// used to resolve any predicates against enumerated fields from the
+    /// "view" (enumeration value) typing they were parsed with to the
+    /// "storage" (enumeration key) typing the query actually evaluates
+    /// against. Only the enumerations referenced by the predicates are
+    /// loaded to do so.
+    pub fn compile(&mut self, schema: &ArraySchema) -> Result<(), CompileError> {
         match self {
             Self::Build(builder) => {
-                *self = Self::Evaluate(builder.compile()?);
+                *self = Self::Evaluate(builder.compile(schema)?);
                 Ok(())
             }
             Self::Evaluate(_) => Err(CompileError::InvalidState),
@@ -127,6 +172,68 @@ impl QueryPredicates {
         }
     }
 
+    /// Returns whether a tile summarized by `stats` could possibly satisfy the predicates.
+    /// A `false` result means the tile is guaranteed not to contain a matching row, so
+    /// [Self::evaluate] can be skipped entirely for it. Only valid from the `Evaluate` state;
+    /// conservatively returns `true` from the `Build` state since there is no predicate to prune.
+    pub fn may_match(&self, stats: &dyn tiledb_expr::pruning::PruningStatistics) -> bool {
+        match self {
+            Self::Build(_) => true,
+            Self::Evaluate(evaluator) => evaluator.may_match(stats),
+        }
+    }
+
+    /// Returns whether the compiled predicates can never match any row, so
+    /// the caller can skip reading tiles for the query entirely. Only
+    /// meaningful from the `Evaluate` state; the `Build` state has not
+    /// simplified anything yet, so this is always `false`.
+    pub fn is_always_false(&self) -> bool {
+        match self {
+            Self::Build(_) => false,
+            Self::Evaluate(evaluator) => evaluator.is_always_false(),
+        }
+    }
+
+    /// Returns the native query condition lowered from the pushdown-eligible
+    /// predicates, if any. Only meaningful from the `Evaluate` state; the
+    /// `Build` state has not attempted the lowering yet.
+    pub fn native_condition(&self) -> Option<&ASTNode> {
+        match self {
+            Self::Build(_) => None,
+            Self::Evaluate(evaluator) => evaluator.native_condition(),
+        }
+    }
+
+    /// Returns whether a tile summarized by `stats` can be skipped entirely,
+    /// i.e. [Self::evaluate]/[Self::evaluate_into_bitmap] would find no
+    /// matching rows in it. Only valid from the `Evaluate` state; there is no
+    /// predicate to prune from the `Build` state, so it is never skippable.
+    pub fn can_skip_tile(&self, stats: &tiledb_expr::pruning::TileStatistics) -> bool {
+        match self {
+            Self::Build(_) => false,
+            Self::Evaluate(evaluator) => evaluator.can_skip_tile(stats),
+        }
+    }
+
+    /// Like [Self::evaluate_into_bitmap], but first consults [Self::can_skip_tile]
+    /// and, if the tile cannot possibly match, clears `bitmap` without
+    /// converting the tile to a [RecordBatch] at all.
+    pub fn evaluate_into_bitmap_with_stats<T>(
+        &self,
+        tile: &ResultTile,
+        stats: &tiledb_expr::pruning::TileStatistics,
+        bitmap: &mut [T],
+    ) -> Result<(), EvaluatePredicateError>
+    where
+        T: Copy + Zero,
+    {
+        if self.can_skip_tile(stats) {
+            bitmap.fill(T::zero());
+            return Ok(());
+        }
+        self.evaluate_into_bitmap(tile, bitmap)
+    }
+
     pub fn evaluate(&self, tile: &ResultTile) -> Result<ColumnarValue, EvaluatePredicateError> {
         match self {
             Self::Build(_) => Err(EvaluatePredicateError::InvalidState),
@@ -180,7 +287,11 @@ impl Builder {
         schema: &ArraySchema,
         which: WhichSchema,
     ) -> Result<Self, tiledb_arrow::schema::Error> {
-        let (arrow_schema, _) = tiledb_arrow::schema::to_arrow(schema, which)?;
+        let (arrow_schema, _) = tiledb_arrow::schema::to_arrow(
+            schema,
+            which,
+            &tiledb_arrow::schema::EnumerationTypeCache::default(),
+        )?;
         let dfschema = {
             // SAFETY: this only errors if the names are not unique,
             // which they will be because `ArraySchema` requires it
@@ -205,6 +316,12 @@ impl Builder {
             .collect()
     }
 
+    /// Registers a user-defined scalar function on the session, usable by
+    /// name in predicate text parsed by [Self::add_text_predicate] afterward.
+    pub fn register_udf(&mut self, udf: datafusion::logical_expr::ScalarUDF) {
+        self.dfsession.register_udf(udf);
+    }
+
     /// Parses a predicate into a logical expression and adds it to the list of predicates to
     /// evaluate.
     pub fn add_text_predicate(&mut self, expr: &str) -> Result<(), AddPredicateError> {
@@ -242,44 +359,138 @@ impl Builder {
     }
 
     /// Returns an `Evaluator` which can evaluate the conjunction of all of the predicates.
-    pub fn compile(&self) -> Result<Evaluator, CompileError> {
+    ///
+    /// `schema` must be the same schema this was constructed from.
+    pub fn compile(&self, schema: &ArraySchema) -> Result<Evaluator, CompileError> {
+        // Resolve any comparisons against enumerated fields to the
+        // enumeration's storage key type. This is done here, rather than
+        // eagerly in `new`, so that only the enumerations actually
+        // referenced by the predicates are loaded.
+        let storage_exprs = self
+            .logical_exprs
+            .iter()
+            .cloned()
+            .map(|e| tiledb_expr::enumeration::rewrite_view_to_storage(e, schema))
+            .collect::<Result<Vec<_>, _>>()?;
+
         let evaluation_schema = {
             let projection_fields = self
                 .field_names()
                 .iter()
-                .map(|fname| self.dfschema.as_arrow().field_with_name(fname))
-                .process_results(|fs| fs.cloned().collect::<Vec<_>>());
-
-            let projection_fields = {
-                // SAFETY: all field names have already been validated as part of the schema
-                projection_fields.unwrap()
-            };
+                .map(|fname| field_for_evaluation(schema, self.dfschema.as_arrow(), fname))
+                .process_results(|fs| fs.collect::<Vec<_>>())?;
 
             // SAFETY: this only errors if the names are not unique,
             // which they will be because `self.field_names()` produces unique field names
             DFSchema::try_from(ArrowSchema::new(projection_fields)).unwrap()
         };
-        let predicate = {
-            let execution_props = ExecutionProps::new();
-            self.logical_exprs
-                .iter()
-                .map(|e| {
-                    datafusion::physical_expr::create_physical_expr(
-                        e,
-                        &evaluation_schema,
-                        &execution_props,
-                    )
-                    .map_err(CompileError::PhysicalExpr)
-                })
-                .process_results(|es| datafusion::physical_expr::conjunction(es))?
+
+        let execution_props = ExecutionProps::new();
+
+        // Constant-fold arithmetic and collapse boolean identities (e.g.
+        // `x AND true`, `1 = 2`) before building the physical expression, so
+        // a predicate that reduces to a literal isn't evaluated per cell on
+        // every tile.
+        let storage_exprs = {
+            let simplify_context =
+                SimplifyContext::new(&execution_props).with_schema(Arc::new(evaluation_schema.clone()));
+            let simplifier = ExprSimplifier::new(simplify_context);
+            storage_exprs
+                .into_iter()
+                .map(|e| simplifier.simplify(e))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(CompileError::Simplify)?
         };
+
+        let is_always_false = storage_exprs
+            .iter()
+            .any(|e| matches!(e, Expr::Literal(ScalarValue::Boolean(Some(false)))));
+
+        // Split the conjunction into a native-pushdown part and a residual
+        // part: each top-level conjunct that can be lowered back into a
+        // query-condition AST (see [tiledb_expr::query_condition::from_datafusion])
+        // is handed to the native evaluator instead of being compiled into
+        // the physical expression below, so simple comparisons skip the
+        // tile-to-`RecordBatch` conversion entirely.
+        let (native_exprs, residual_exprs): (Vec<_>, Vec<_>) = storage_exprs
+            .iter()
+            .cloned()
+            .flat_map(split_conjunction)
+            .partition(|e| tiledb_expr::query_condition::from_datafusion(schema, e).is_ok());
+
+        let native_condition = datafusion::logical_expr::utils::conjunction(native_exprs)
+            .map(|native_expr| {
+                tiledb_expr::query_condition::from_datafusion(schema, &native_expr)
+                    .map_err(CompileError::QueryCondition)
+            })
+            .transpose()?;
+
+        // Kept as a list rather than pre-combined into one conjunction so
+        // `Evaluator::evaluate_into_bitmap` can evaluate them in order
+        // against a shrinking selection vector.
+        let predicates = residual_exprs
+            .iter()
+            .map(|e| {
+                datafusion::physical_expr::create_physical_expr(e, &evaluation_schema, &execution_props)
+                    .map_err(CompileError::PhysicalExpr)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let predicate = datafusion::physical_expr::conjunction(predicates.iter().cloned());
+
         Ok(Evaluator {
             dfschema: evaluation_schema,
+            storage_exprs,
             predicate,
+            predicates,
+            is_always_false,
+            native_condition,
         })
     }
 }
 
+/// Returns the top-level conjuncts of `expr`, splitting nested `AND` nodes so
+/// each one can be independently considered for native pushdown in
+/// [Builder::compile].
+fn split_conjunction(expr: Expr) -> Vec<Expr> {
+    match expr {
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::And,
+            right,
+        }) => {
+            let mut exprs = split_conjunction(*left);
+            exprs.extend(split_conjunction(*right));
+            exprs
+        }
+        other => vec![other],
+    }
+}
+
+/// Returns the Arrow field which should back `fname` during evaluation: its
+/// "storage" (enumeration key) type if `fname` is enumerated, otherwise its
+/// existing "view" type.
+fn field_for_evaluation(
+    schema: &ArraySchema,
+    view_schema: &ArrowSchema,
+    fname: &str,
+) -> Result<ArrowField, CompileError> {
+    let view_field = {
+        // SAFETY: all field names have already been validated as part of the schema
+        view_schema.field_with_name(fname).unwrap()
+    };
+    if !view_field.metadata().contains_key("enumeration") {
+        return Ok(view_field.clone());
+    }
+
+    let Some(field) = schema.field(fname) else {
+        // SAFETY: field names come from the schema this predicate set was built against
+        unreachable!("field {fname:?} not found in schema")
+    };
+    let storage_type = tiledb_arrow::schema::arrow_datatype(field.datatype(), field.cell_val_num())?;
+    Ok(ArrowField::new(fname, storage_type, true).with_metadata(view_field.metadata().clone()))
+}
+
 pub struct Evaluator {
     /// Array schema mapped onto DataFusion data types; this is a projection of the full schema
     /// consisting only of the fields which are used to evaluate `self.predicate`.
@@ -287,8 +498,30 @@ pub struct Evaluator {
     /// columns, so to avoid extra conversions (which may allocate memory) we do not
     /// want to keep all of the fields here.
     dfschema: DFSchema,
-    /// Expression evaluator which evaluates all predicates as a conjunction.
+    /// The predicates in storage (enumeration key) typing, i.e. after the
+    /// same rewrite used to build `predicate`. Kept separately so they can
+    /// be walked by [tiledb_expr::pruning] without re-running the predicate
+    /// itself.
+    storage_exprs: Vec<Expr>,
+    /// Expression evaluator which evaluates all residual predicates as a
+    /// conjunction in one pass; used by [Self::evaluate].
     predicate: Arc<dyn PhysicalExpr>,
+    /// The same residual predicates as `predicate`, kept un-combined and in
+    /// order so [Self::evaluate_into_bitmap] can evaluate them one at a time
+    /// against a shrinking selection vector instead of re-running the full
+    /// conjunction over every cell.
+    predicates: Vec<Arc<dyn PhysicalExpr>>,
+    /// Whether one of `storage_exprs` constant-folded to a literal `false`
+    /// during [Builder::compile]'s simplification pass, meaning the query
+    /// can never match any row.
+    is_always_false: bool,
+    /// The conjunction of top-level predicates which [Builder::compile] was
+    /// able to lower back into a native query condition, if any. `predicate`
+    /// only evaluates the remaining residual conjuncts, so a caller with a
+    /// native query-condition evaluator available can push this down instead
+    /// of converting the corresponding tile columns to a [RecordBatch] at
+    /// all; this crate has no such reader to hand it to yet.
+    native_condition: Option<cxx::UniquePtr<ASTNode>>,
 }
 
 impl Evaluator {
@@ -301,6 +534,58 @@ impl Evaluator {
             .collect::<Vec<_>>()
     }
 
+    /// Returns whether the predicates can never match any row, having
+    /// constant-folded to `false` during [Builder::compile]. A caller can use
+    /// this to skip reading tiles for the query entirely, rather than relying
+    /// on the per-tile pruning in [Self::evaluate_into_bitmap]/[Self::can_skip_tile].
+    pub fn is_always_false(&self) -> bool {
+        self.is_always_false
+    }
+
+    /// Returns the native query condition lowered from the pushdown-eligible
+    /// predicates, if any could be represented that way. See the
+    /// `native_condition` field docs for how this relates to [Self::evaluate].
+    pub fn native_condition(&self) -> Option<&ASTNode> {
+        self.native_condition.as_ref().and_then(|ast| ast.as_ref())
+    }
+
+    /// Returns whether a tile summarized by `stats` could possibly satisfy
+    /// the predicates, without reading it. A `false` result means the tile
+    /// is guaranteed not to contain a matching row, so [Self::evaluate] can
+    /// be skipped entirely for it.
+    pub fn may_match(&self, stats: &dyn tiledb_expr::pruning::PruningStatistics) -> bool {
+        self.storage_exprs
+            .iter()
+            .all(|e| tiledb_expr::pruning::may_match(e, stats))
+    }
+
+    /// The tile-skip fast path: returns whether a tile summarized by `stats`
+    /// is guaranteed not to contain a matching row, in which case
+    /// [Self::evaluate]/[Self::evaluate_into_bitmap] can be skipped entirely
+    /// for it, avoiding the cost of materializing it into a [RecordBatch].
+    pub fn can_skip_tile(&self, stats: &tiledb_expr::pruning::TileStatistics) -> bool {
+        !self.may_match(stats)
+    }
+
+    /// Like [Self::evaluate_into_bitmap], but first checks [Self::can_skip_tile]
+    /// and, if the tile cannot possibly match, clears `bitmap` without
+    /// converting the tile to a [RecordBatch] at all.
+    pub fn evaluate_into_bitmap_with_stats<T>(
+        &self,
+        tile: &ResultTile,
+        stats: &tiledb_expr::pruning::TileStatistics,
+        bitmap: &mut [T],
+    ) -> Result<(), EvaluatePredicateError>
+    where
+        T: Copy + Zero,
+    {
+        if self.can_skip_tile(stats) {
+            bitmap.fill(T::zero());
+            return Ok(());
+        }
+        self.evaluate_into_bitmap(tile, bitmap)
+    }
+
     pub fn evaluate(&self, tile: &ResultTile) -> Result<ColumnarValue, EvaluatePredicateError> {
         let rb = unsafe {
             // SAFETY: "This function is safe to call as long as the returned
@@ -313,6 +598,15 @@ impl Evaluator {
             .map_err(EvaluatePredicateError::Evaluate)
     }
 
+    /// Evaluates `self.predicates` in order against a selection vector that
+    /// starts as the currently-set positions of `bitmap` and only shrinks:
+    /// each predicate is evaluated only on rows that survived the previous
+    /// one (via a gathered [RecordBatch]), and evaluation stops as soon as no
+    /// rows survive. This means a cell the caller already excluded (e.g. a
+    /// historical query condition or a timestamp duplicate) is never
+    /// re-evaluated, and for selective predicates on wide tiles the Arrow
+    /// work done is proportional to the surviving cells rather than
+    /// `num_predicates * num_cells`.
     pub fn evaluate_into_bitmap<T>(
         &self,
         tile: &ResultTile,
@@ -321,51 +615,69 @@ impl Evaluator {
     where
         T: Copy + Zero,
     {
-        // TODO: consider not evaluating on cells where the bitmap is already set.
-        // This might happen if there is a historical query condition or if there
-        // is timestamp duplication.
-
-        let result = self.evaluate(tile)?;
-        match result {
-            ColumnarValue::Scalar(s) => match s {
-                ScalarValue::Boolean(Some(true)) => {
-                    // all cells pass predicates, no need to update bitmap
-                    Ok(())
-                }
-                ScalarValue::Boolean(Some(false)) => {
-                    // no cells pass predicates, clear bitmap
-                    bitmap.fill(T::zero());
-                    Ok(())
-                }
-                ScalarValue::Null | ScalarValue::Boolean(None) => {
-                    // no cells pass predicates, clear bitmap
-                    bitmap.fill(T::zero());
-                    Ok(())
-                }
-                _ => {
+        let mut selection = bitmap
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| !v.is_zero())
+            .map(|(i, _)| i as u32)
+            .collect::<Vec<_>>();
+        if selection.is_empty() {
+            return Ok(());
+        }
+        let initial_selection = selection.clone();
+
+        let rb = unsafe {
+            // SAFETY: "This function is safe to call as long as the returned
+            // RecordBatch is not used after the ResultTile is destructed."
+            // The RecordBatch only lives in this stack frame, so we will follow this contract.
+            tiledb_arrow::record_batch::to_record_batch(self.dfschema.inner(), tile)?
+        };
+
+        for predicate in &self.predicates {
+            if selection.is_empty() {
+                break;
+            }
+
+            let indices = arrow::array::UInt32Array::from(selection.clone());
+            let gathered = arrow::compute::take_record_batch(&rb, &indices)
+                .map_err(EvaluatePredicateError::Gather)?;
+
+            selection = match predicate
+                .evaluate(&gathered)
+                .map_err(EvaluatePredicateError::Evaluate)?
+            {
+                ColumnarValue::Scalar(ScalarValue::Boolean(Some(true))) => selection,
+                ColumnarValue::Scalar(ScalarValue::Boolean(Some(false)))
+                | ColumnarValue::Scalar(ScalarValue::Null)
+                | ColumnarValue::Scalar(ScalarValue::Boolean(None)) => Vec::new(),
+                ColumnarValue::Scalar(_) => {
                     // should not be reachable due to return type check in `Builder::add_predicate`
                     unreachable!()
                 }
-            },
-            ColumnarValue::Array(a) => {
-                if *a.data_type() == DataType::Boolean {
+                ColumnarValue::Array(a) if *a.data_type() == DataType::Boolean => {
                     let bools = arrow::array::as_boolean_array(&a);
-                    for (i, b) in bools.iter().enumerate() {
-                        if !matches!(b, Some(true)) {
-                            bitmap[i] = T::zero();
-                        }
-                    }
-                    Ok(())
-                } else if *a.data_type() == DataType::Null {
-                    // no cells pass predicates, clear bitmap
-                    bitmap.fill(T::zero());
-                    Ok(())
-                } else {
+                    selection
+                        .into_iter()
+                        .zip(bools.iter())
+                        .filter_map(|(row, keep)| matches!(keep, Some(true)).then_some(row))
+                        .collect()
+                }
+                ColumnarValue::Array(a) if *a.data_type() == DataType::Null => Vec::new(),
+                ColumnarValue::Array(_) => {
                     // should not be reachable due to return type check in `Builder::add_predicate`
                     unreachable!()
                 }
+            };
+        }
+
+        let surviving = selection.into_iter().collect::<std::collections::HashSet<_>>();
+        for row in initial_selection {
+            if !surviving.contains(&row) {
+                bitmap[row as usize] = T::zero();
             }
         }
+
+        Ok(())
     }
 }
 
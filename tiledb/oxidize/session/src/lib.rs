@@ -3,8 +3,10 @@ mod ffi {
     #[namespace = "tiledb::sm"]
     extern "C++" {
         include!("tiledb/sm/array_schema/array_schema.h");
+        include!("tiledb/sm/query/ast/query_ast.h");
 
         type ArraySchema = tiledb_cxx_interface::sm::array_schema::ArraySchema;
+        type ASTNode = tiledb_cxx_interface::sm::query::ast::ASTNode;
     }
 
     #[namespace = "tiledb::oxidize::datafusion::logical_expr"]
@@ -24,6 +26,19 @@ mod ffi {
             expr: &str,
             array_schema: &ArraySchema,
         ) -> Result<Box<ExternLogicalExpr>>;
+
+        /// Parses `expr` and lowers it to a native query condition, for the
+        /// caller to push down into the read path instead of evaluating it
+        /// with DataFusion. Fails (rather than falling back silently) if any
+        /// part of `expr` cannot be represented as a query condition, e.g. a
+        /// `LIKE` or an arithmetic sub-expression -- the caller is expected
+        /// to fall back to [Self::parse_expr] in that case.
+        #[cxx_name = "parse_query_condition"]
+        fn parse_query_condition_ffi(
+            &self,
+            expr: &str,
+            array_schema: &ArraySchema,
+        ) -> Result<UniquePtr<ASTNode>>;
     }
 }
 
@@ -46,14 +61,19 @@ fn new_session() -> Box<Session> {
     Box::new(Session::new())
 }
 
+mod table_provider;
+
 use datafusion::common::DFSchema;
 use datafusion::common::tree_node::TreeNode;
 use datafusion::execution::context::SessionContext;
 use datafusion::execution::session_state::SessionStateBuilder;
 use datafusion::logical_expr::Expr;
 use tiledb_cxx_interface::sm::array_schema::ArraySchema;
+use tiledb_cxx_interface::sm::query::ast::ASTNode;
 use tiledb_expr::LogicalExpr;
 
+pub use table_provider::{TableProviderError, TileDbTableProvider};
+
 #[derive(Debug, thiserror::Error)]
 pub enum ParseExprError {
     #[error("Schema error: {0}")]
@@ -62,16 +82,25 @@ pub enum ParseExprError {
     Parse(#[source] datafusion::common::DataFusionError),
     #[error("Type coercion error: {0}")]
     TypeCoercion(#[source] datafusion::common::DataFusionError),
+    #[error("Enumeration error: {0}")]
+    Enumeration(#[from] tiledb_expr::enumeration::Error),
+    #[error("Query condition lowering error: {0}")]
+    QueryCondition(#[source] tiledb_expr::query_condition::Error),
+    #[error("Table provider error: {0}")]
+    TableProvider(#[from] table_provider::TableProviderError),
+    #[error("Failed to register table: {0}")]
+    RegisterTable(#[source] datafusion::common::DataFusionError),
 }
 
 /// Wraps a DataFusion [SessionContext] for passing across the FFI boundary.
-pub struct Session(pub SessionContext);
+pub struct Session(pub SessionContext, tiledb_arrow::schema::EnumerationTypeCache);
 
 impl Session {
     pub fn new() -> Self {
-        Self(SessionContext::from(
-            SessionStateBuilder::new_with_default_features().build(),
-        ))
+        Self(
+            SessionContext::from(SessionStateBuilder::new_with_default_features().build()),
+            Default::default(),
+        )
     }
 
     fn parse_expr_ffi(
@@ -83,8 +112,46 @@ impl Session {
         Ok(Box::new(ExternLogicalExpr(LogicalExpr(e))))
     }
 
+    fn parse_query_condition_ffi(
+        &self,
+        expr: &str,
+        array_schema: &ArraySchema,
+    ) -> Result<cxx::UniquePtr<ASTNode>, ParseExprError> {
+        let parsed = self.parse_expr(expr, array_schema)?;
+
+        // Resolve comparisons against enumerated fields to the enumeration's
+        // storage key type, matching the schema that query conditions are
+        // actually evaluated against.
+        let storage_expr = tiledb_expr::enumeration::rewrite_view_to_storage(parsed, array_schema)?;
+
+        tiledb_expr::query_condition::from_datafusion(array_schema, &storage_expr)
+            .map_err(ParseExprError::QueryCondition)
+    }
+
+    /// Registers `array_schema` as a table named `table_name`, so a
+    /// subsequent `self.0.sql(...)` can run a complete `SELECT ... WHERE ...`
+    /// against it instead of only parsing a standalone predicate via
+    /// [Self::parse_expr]/[Self::parse_query_condition_ffi]. See
+    /// [TileDbTableProvider] for which parts of the resulting query plan
+    /// actually execute today.
+    pub fn register_array(
+        &self,
+        table_name: &str,
+        array_schema: cxx::SharedPtr<ArraySchema>,
+    ) -> Result<(), ParseExprError> {
+        let provider = TileDbTableProvider::try_new(array_schema)?;
+        self.0
+            .register_table(table_name, std::sync::Arc::new(provider))
+            .map_err(ParseExprError::RegisterTable)?;
+        Ok(())
+    }
+
     fn parse_expr(&self, expr: &str, array_schema: &ArraySchema) -> Result<Expr, ParseExprError> {
-        let (arrow_schema, _) = tiledb_arrow::schema::to_arrow(array_schema)?;
+        let (arrow_schema, _) = tiledb_arrow::schema::to_arrow(
+            array_schema,
+            tiledb_arrow::ffi::WhichSchema::View,
+            &self.1,
+        )?;
         let df_schema = {
             // SAFETY: this only errors if the names are not unique,
             // which they will be because `ArraySchema` requires it
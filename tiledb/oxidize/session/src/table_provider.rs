@@ -0,0 +1,237 @@
+//! A DataFusion [TableProvider] backed by a TileDB [ArraySchema], so a
+//! caller can run a complete `SELECT ... WHERE ...` against an array via
+//! [SessionContext::sql] rather than only parsing a standalone predicate
+//! (see [crate::Session::parse_expr]/[crate::Session::parse_query_condition_ffi]).
+//!
+//! Schema derivation, projection pushdown, and filter pushdown classification
+//! are all real: they reuse the same `tiledb_arrow::schema::project_arrow`
+//! and `tiledb_expr::query_condition::from_datafusion` machinery as
+//! `query-predicates::Builder::compile`. Filters are split on their
+//! top-level `AND`s before classification, so e.g. `a = 1 AND b LIKE '%x%'`
+//! still gets `a = 1` pushed down as a native query condition instead of
+//! the whole filter being rejected over the unsupported `LIKE` conjunct.
+//! [TileDbTableProvider::scan] itself cannot read any data yet, though:
+//! unlike `query-predicates`, which is handed already-materialized
+//! [ResultTile]s by a C++-side reader, nothing in `tiledb_cxx_interface`
+//! submits a native `Query` or streams back its results, so there is no
+//! read to drive from here. [Self::scan] reports that honestly via
+//! `DataFusionError::NotImplemented` rather than silently returning no rows,
+//! even once it has assembled the native condition it would have pushed.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::catalog::{Session as DFSession, TableProvider};
+use datafusion::error::{DataFusionError, Result as DFResult};
+use datafusion::logical_expr::{BinaryExpr, Expr, Operator, TableProviderFilterPushDown, TableType};
+use datafusion::physical_plan::ExecutionPlan;
+
+use tiledb_arrow::schema::{EnumerationTypeCache, EnumerationTypeResolver, WhichSchema};
+use tiledb_cxx_interface::sm::array_schema::{ArraySchema, Field};
+
+#[derive(Debug, thiserror::Error)]
+pub enum TableProviderError {
+    #[error("Schema error: {0}")]
+    Schema(#[from] tiledb_arrow::schema::Error),
+}
+
+/// A DataFusion [TableProvider] over a TileDB array, registered on a
+/// [crate::Session] by [crate::Session::register_array].
+pub struct TileDbTableProvider {
+    array_schema: cxx::SharedPtr<ArraySchema>,
+    schema: SchemaRef,
+    enumerations: EnumerationTypeCache,
+}
+
+impl TileDbTableProvider {
+    pub fn try_new(array_schema: cxx::SharedPtr<ArraySchema>) -> Result<Self, TableProviderError> {
+        let enumerations = EnumerationTypeCache::default();
+        let schema = {
+            let schema_ref = array_schema
+                .as_ref()
+                .expect("array_schema_to_shared always returns a non-null SharedPtr");
+            // `to_arrow` only records each enumeration's value type into
+            // `enumerations` as a side effect of computing field types in the
+            // same pass, so a field referencing an enumeration that hasn't
+            // been recorded yet resolves to `ArrowDataType::Null` on a cold
+            // cache. Warm it up first so the schema we actually cache has
+            // every enumerated field correctly resolved to its `Dictionary`
+            // type; this only reads each enumeration's `(Datatype,
+            // CellValNum)`, not its (potentially large) decoded variant
+            // array, so it's cheap even with many/large enumerations.
+            record_enumeration_types(schema_ref, &enumerations);
+            let (schema, _) = tiledb_arrow::schema::to_arrow(schema_ref, WhichSchema::View, &enumerations)?;
+            Arc::new(schema)
+        };
+
+        Ok(Self {
+            array_schema,
+            schema,
+            enumerations,
+        })
+    }
+
+    fn array_schema(&self) -> &ArraySchema {
+        self.array_schema
+            .as_ref()
+            .expect("array_schema_to_shared always returns a non-null SharedPtr")
+    }
+
+    /// Pushes a predicate to TileDB's native query condition, resolving
+    /// enumerated-field comparisons to their storage type first. Returns
+    /// `Err` for anything `tiledb_expr::query_condition::from_datafusion`
+    /// cannot represent, e.g. a `LIKE` or an arithmetic sub-expression.
+    fn native_condition(
+        &self,
+        filter: &Expr,
+    ) -> Result<cxx::UniquePtr<tiledb_cxx_interface::sm::query::ast::ASTNode>, ()> {
+        let storage_expr =
+            tiledb_expr::enumeration::rewrite_view_to_storage(filter.clone(), self.array_schema())
+                .map_err(|_| ())?;
+        tiledb_expr::query_condition::from_datafusion(self.array_schema(), &storage_expr).map_err(|_| ())
+    }
+
+    /// Classifies how much of `filter` can be pushed down as a native query
+    /// condition: [TableProviderFilterPushDown::Exact] if every top-level
+    /// conjunct translates, [TableProviderFilterPushDown::Inexact] if only
+    /// some do (DataFusion still re-checks the whole filter itself in that
+    /// case), and [TableProviderFilterPushDown::Unsupported] if none do.
+    fn classify_filter(&self, filter: &Expr) -> TableProviderFilterPushDown {
+        let conjuncts = split_conjuncts(filter);
+        let supported = conjuncts
+            .iter()
+            .filter(|c| self.native_condition(c).is_ok())
+            .count();
+        match supported {
+            0 => TableProviderFilterPushDown::Unsupported,
+            n if n == conjuncts.len() => TableProviderFilterPushDown::Exact,
+            _ => TableProviderFilterPushDown::Inexact,
+        }
+    }
+
+    /// Builds the native query condition for whichever top-level conjuncts
+    /// of `filters` translate, ANDing them together. Returns `None` if none
+    /// of them do.
+    fn native_conditions(
+        &self,
+        filters: &[Expr],
+    ) -> Option<cxx::UniquePtr<tiledb_cxx_interface::sm::query::ast::ASTNode>> {
+        filters
+            .iter()
+            .flat_map(|f| split_conjuncts(f))
+            .filter_map(|c| self.native_condition(c).ok())
+            .reduce(tiledb_expr::query_condition::and)
+    }
+}
+
+/// Primes `resolver` with the `(Datatype, CellValNum)` of every enumeration
+/// referenced by a field of `array_schema`, without decoding any
+/// enumeration's (potentially large) variant array -- unlike `to_arrow`,
+/// which only records an enumeration's type as a side effect of loading its
+/// variants.
+fn record_enumeration_types(array_schema: &ArraySchema, resolver: &EnumerationTypeCache) {
+    for field in array_schema.fields() {
+        let Some(Ok(ename)) = field.enumeration_name() else {
+            continue;
+        };
+        if !array_schema.has_enumeration(ename) {
+            continue;
+        }
+        let enumeration = array_schema.enumeration(ename);
+        if enumeration.is_null() {
+            continue;
+        }
+        resolver.record(ename, enumeration.datatype(), enumeration.cell_val_num());
+    }
+}
+
+/// Splits `expr` at top-level `AND`s into its individual conjuncts, e.g.
+/// `a = 1 AND b = 2 AND c = 3` becomes `[a = 1, b = 2, c = 3]`. An
+/// expression with no top-level `AND` is returned as the single conjunct
+/// `[expr]`, so every filter can be classified/translated conjunct-by-conjunct
+/// uniformly regardless of whether it is itself a conjunction.
+fn split_conjuncts(expr: &Expr) -> Vec<&Expr> {
+    match expr {
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::And,
+            right,
+        }) => {
+            let mut conjuncts = split_conjuncts(left);
+            conjuncts.extend(split_conjuncts(right));
+            conjuncts
+        }
+        other => vec![other],
+    }
+}
+
+#[async_trait]
+impl TableProvider for TileDbTableProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    /// Only the non-read parts of this are implemented; see the module docs.
+    async fn scan(
+        &self,
+        _state: &dyn DFSession,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> DFResult<Arc<dyn ExecutionPlan>> {
+        // Projection pushdown: only the fields DataFusion actually asked for
+        // are selected out of the array schema, driven directly by
+        // `ArraySchema::fields()` rather than the already-narrowed
+        // `self.schema`.
+        let projected_field_names: Option<Vec<&str>> = projection.map(|indices| {
+            indices
+                .iter()
+                .map(|&i| self.schema.field(i).name().as_str())
+                .collect()
+        });
+        let select = |f: &Field| match (&projected_field_names, f.name()) {
+            (Some(names), Ok(name)) => names.contains(&name),
+            (None, _) => true,
+            (Some(_), Err(_)) => false,
+        };
+        let (_projected_schema, _enumerations) = tiledb_arrow::schema::project_arrow(
+            self.array_schema(),
+            WhichSchema::View,
+            &self.enumerations,
+            select,
+        )
+        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+        // Filter pushdown: assemble the native condition for whichever
+        // conjuncts of `filters` translate, the same way
+        // `supports_filters_pushdown` classifies them. Conjuncts that don't
+        // translate are simply left out here; DataFusion re-evaluates the
+        // whole filter itself whenever it classified it `Inexact` rather
+        // than `Exact`, so there is no residual to track on this side.
+        let _native_condition = self.native_conditions(filters);
+
+        Err(DataFusionError::NotImplemented(
+            "TileDbTableProvider::scan: reading from a TileDB array requires a native \
+             Query FFI binding (submit + stream ResultTiles), which tiledb_cxx_interface \
+             does not yet expose"
+                .to_string(),
+        ))
+    }
+
+    fn supports_filters_pushdown(&self, filters: &[&Expr]) -> DFResult<Vec<TableProviderFilterPushDown>> {
+        Ok(filters
+            .iter()
+            .map(|filter| self.classify_filter(filter))
+            .collect())
+    }
+}
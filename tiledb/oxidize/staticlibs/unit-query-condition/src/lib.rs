@@ -33,6 +33,8 @@ mod ffi {
     extern "Rust" {
         fn examples_query_condition_datafusion() -> Result<bool>;
         fn proptest_query_condition_datafusion() -> Result<bool>;
+        fn proptest_query_condition_simplify() -> Result<bool>;
+        fn proptest_query_condition_element() -> Result<bool>;
     }
 }
 
@@ -54,6 +56,7 @@ use tiledb_pod::array::schema::SchemaData;
 use tiledb_pod::array::schema::strategy::Requirements as SchemaRequirements;
 use tiledb_test_cells::strategy::{CellsParameters, CellsStrategySchema, SchemaWithDomain};
 use tiledb_test_cells::{Cells, FieldData};
+use tiledb_test_query_condition::Condition;
 
 fn instance_query_condition_datafusion(
     schema: &SchemaData,
@@ -275,6 +278,20 @@ fn examples_query_condition_datafusion_impl() -> anyhow::Result<bool> {
         let result = ffi::instance_query_condition_datafusion(&cxx_schema, &cxx_tile, &cxx_ast)?;
         assert_eq!(result.as_slice(), vec![0, 0, 1, 0, 1, 1, 0, 0, 1, 0]);
     }
+    // d IN (1, 3, 5)
+    {
+        let ast = QueryConditionExpr::field("d").is_in([1u64, 3, 5]);
+        let cxx_ast = tiledb_test_query_condition::ast_from_query_condition(&ast)?;
+        let result = ffi::instance_query_condition_datafusion(&cxx_schema, &cxx_tile, &cxx_ast)?;
+        assert_eq!(result.as_slice(), vec![1, 0, 1, 0, 1, 0, 0, 0, 0, 0]);
+    }
+    // v NOT IN ('one', 'onetwo')
+    {
+        let ast = QueryConditionExpr::field("v").not_in(["one", "onetwo"]);
+        let cxx_ast = tiledb_test_query_condition::ast_from_query_condition(&ast)?;
+        let result = ffi::instance_query_condition_datafusion(&cxx_schema, &cxx_tile, &cxx_ast)?;
+        assert_eq!(result.as_slice(), vec![0, 0, 0, 0, 1, 0, 1, 0, 1, 1]);
+    }
 
     Ok(true)
 }
@@ -306,34 +323,120 @@ fn cells_ensure_utf8(schema: &SchemaData, cells: Cells) -> Cells {
     Cells::new(new_fields)
 }
 
-/// Returns a [Strategy] which produces inputs to `instance_query_condition_datafusion`.
-fn strat_query_condition_datafusion()
--> impl Strategy<Value = (Rc<SchemaData>, Rc<Cells>, Vec<QueryConditionExpr>)> {
+/// Returns the distinct values of `values`, in first-seen order.
+fn distinct_values<T: Clone + PartialEq>(values: &[T]) -> Vec<T> {
+    let mut out = Vec::<T>::new();
+    for v in values {
+        if !out.contains(v) {
+            out.push(v.clone());
+        }
+    }
+    out
+}
+
+/// Returns a [Strategy] which builds a `field IN (...)` / `field NOT IN (...)`
+/// condition against one of `cells`' own fields, drawing its candidate set
+/// from `cells`' actual distinct values for that field so the condition has
+/// a real chance of selecting some cells (a candidate set drawn out of thin
+/// air would almost always miss every row). Only `UInt64` and string (stored
+/// as [FieldData::VecUInt8]) fields are covered, matching the only two kinds
+/// `QueryConditionExpr::is_in`/`not_in` are exercised against in
+/// `examples_query_condition_datafusion_impl`; returns `None` if `cells` has
+/// no such field, or none with more than one distinct value.
+fn strat_set_membership_condition(cells: &Cells) -> Option<impl Strategy<Value = QueryConditionExpr>> {
+    let mut per_field = Vec::new();
+
+    for (field, data) in cells.fields().iter() {
+        let strat: Option<proptest::strategy::BoxedStrategy<QueryConditionExpr>> = match data {
+            FieldData::UInt64(values) => {
+                let distinct = distinct_values(values);
+                (distinct.len() > 1).then(|| {
+                    let field = field.clone();
+                    let max_set_size = distinct.len().min(3);
+                    (proptest::sample::subsequence(distinct, 1..=max_set_size), any::<bool>())
+                        .prop_map(move |(candidates, negate)| {
+                            if negate {
+                                QueryConditionExpr::field(field.clone()).not_in(candidates)
+                            } else {
+                                QueryConditionExpr::field(field.clone()).is_in(candidates)
+                            }
+                        })
+                        .boxed()
+                })
+            }
+            FieldData::VecUInt8(values) => {
+                let distinct = distinct_values(values)
+                    .into_iter()
+                    .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                    .collect::<Vec<_>>();
+                (distinct.len() > 1).then(|| {
+                    let field = field.clone();
+                    let max_set_size = distinct.len().min(3);
+                    (proptest::sample::subsequence(distinct, 1..=max_set_size), any::<bool>())
+                        .prop_map(move |(candidates, negate)| {
+                            if negate {
+                                QueryConditionExpr::field(field.clone()).not_in(candidates)
+                            } else {
+                                QueryConditionExpr::field(field.clone()).is_in(candidates)
+                            }
+                        })
+                        .boxed()
+                })
+            }
+            _ => None,
+        };
+        per_field.extend(strat);
+    }
+
+    (!per_field.is_empty()).then(|| proptest::strategy::Union::new(per_field))
+}
+
+/// Returns a [Strategy] which generates a schema and a matching set of
+/// cells, shared by `strat_query_condition_datafusion` and
+/// `strat_query_condition_element` so neither has to re-derive the other's
+/// generation parameters.
+fn strat_schema_and_cells() -> impl Strategy<Value = (Rc<SchemaData>, Cells)> {
     let schema_params = SchemaRequirements {
-        // NB: enumerations are not working properly with `Cells`.
-        // The best thing to do would be to remove `Cells` and just use `RecordBatch`,
-        // so we're not going to worry about it since we do have some test examples
-        // with enumerations.
+        // NB: `schema_from_pod` now builds and attaches real enumerations, but
+        // `Cells`/`result_tile_from_cells` still store attribute values as their
+        // user-facing type rather than the enumeration's integer index, so a
+        // generated enumerated attribute would write values the C++ evaluator
+        // can't interpret. The best thing to do would be to remove `Cells` and
+        // just use `RecordBatch`, so we're not going to worry about it since we
+        // do have some test examples with enumerations.
         attribute_enumeration_likelihood: 0.0,
         ..Default::default()
     };
-    any_with::<SchemaData>(schema_params.into())
-        .prop_flat_map(|schema| {
-            let schema = Rc::new(schema);
-            let schema_move_into_strat = Rc::clone(&schema);
-            let strat_cells = any_with::<Cells>(CellsParameters {
-                schema: Some(CellsStrategySchema::WriteSchema(Rc::clone(&schema))),
-                ..Default::default()
-            })
-            .prop_map(move |cells| cells_ensure_utf8(&schema_move_into_strat, cells));
-            (Just(schema), strat_cells)
+    any_with::<SchemaData>(schema_params.into()).prop_flat_map(|schema| {
+        let schema = Rc::new(schema);
+        let schema_move_into_strat = Rc::clone(&schema);
+        let strat_cells = any_with::<Cells>(CellsParameters {
+            schema: Some(CellsStrategySchema::WriteSchema(Rc::clone(&schema))),
+            ..Default::default()
         })
+        .prop_map(move |cells| cells_ensure_utf8(&schema_move_into_strat, cells));
+        (Just(schema), strat_cells)
+    })
+}
+
+/// Returns a [Strategy] which produces inputs to `instance_query_condition_datafusion`.
+fn strat_query_condition_datafusion()
+-> impl Strategy<Value = (Rc<SchemaData>, Rc<Cells>, Vec<QueryConditionExpr>)> {
+    strat_schema_and_cells()
         .prop_flat_map(|(schema, cells)| {
             let cells = Rc::new(cells);
             let strat_qc = any_with::<QueryConditionExpr>(QueryConditionParameters {
                 domain: Some(Rc::new(SchemaWithDomain::new(Rc::clone(&schema), &cells))),
                 ..Default::default()
-            });
+            })
+            .boxed();
+            // Mixed in alongside the arbitrary `QueryConditionExpr` generator
+            // so that IN/NOT_IN conditions, which nothing else here
+            // generates, get meaningful fuzz coverage too.
+            let strat_qc = match strat_set_membership_condition(&cells) {
+                Some(strat_in) => prop_oneof![4 => strat_qc, 1 => strat_in.boxed()].boxed(),
+                None => strat_qc,
+            };
             (Just(schema), Just(cells), strat_qc.prop_map(|qc| vec![qc]))
         })
 }
@@ -359,3 +462,176 @@ fn proptest_query_condition_datafusion() -> anyhow::Result<bool> {
         Err(e) => Err(anyhow!(e.to_string())),
     }
 }
+
+/// Evaluates `condition` against `schema`/`cells`, then asserts that
+/// `tiledb_test_query_condition::simplify`-ing `condition` before converting
+/// it to an AST produces byte-for-byte the same selection bitmap.
+fn instance_query_condition_simplify(
+    schema: &SchemaData,
+    cells: &Cells,
+    condition: &QueryConditionExpr,
+) -> anyhow::Result<()> {
+    let cxx_schema = tiledb_test_array_schema::schema_from_pod(schema)?;
+    let cxx_tile = tiledb_test_result_tile::result_tile_from_cells(&cxx_schema, cells)?;
+
+    let original_ast = tiledb_test_query_condition::ast_from_query_condition(condition)?;
+    let original_bitmap =
+        ffi::instance_query_condition_datafusion(&cxx_schema, &cxx_tile, &original_ast)?
+            .as_slice()
+            .to_vec();
+
+    let simplified_bitmap = match tiledb_test_query_condition::simplify(condition.clone()) {
+        tiledb_test_query_condition::Simplified::Literal(selected) => {
+            vec![selected as u8; original_bitmap.len()]
+        }
+        tiledb_test_query_condition::Simplified::Expr(simplified) => {
+            let ast = tiledb_test_query_condition::ast_from_query_condition(&simplified)?;
+            ffi::instance_query_condition_datafusion(&cxx_schema, &cxx_tile, &ast)?
+                .as_slice()
+                .to_vec()
+        }
+    };
+
+    anyhow::ensure!(
+        original_bitmap == simplified_bitmap,
+        "simplifying {condition:?} changed the selection bitmap: {original_bitmap:?} vs {simplified_bitmap:?}"
+    );
+    Ok(())
+}
+
+/// Returns a [Strategy] which produces inputs to `instance_query_condition_simplify`,
+/// reusing `strat_query_condition_datafusion`'s schema/cells generation since it
+/// already generates exactly one [QueryConditionExpr] per case.
+fn strat_query_condition_simplify()
+-> impl Strategy<Value = (Rc<SchemaData>, Rc<Cells>, QueryConditionExpr)> {
+    strat_query_condition_datafusion().prop_map(|(schema, cells, mut condition)| {
+        let condition = condition
+            .pop()
+            .expect("strat_query_condition_datafusion always produces exactly one condition");
+        (schema, cells, condition)
+    })
+}
+
+/// Evaluates `instance_query_condition_simplify` against values drawn randomly
+/// from `strat_query_condition_simplify`.
+///
+/// Returns `Ok` if all test cases were successful and `Err` otherwise,
+/// logging the "minimum" failing example to standard output.
+fn proptest_query_condition_simplify() -> anyhow::Result<bool> {
+    let mut runner = TestRunner::new(proptest::test_runner::Config {
+        cases: 2048,
+        ..Default::default()
+    });
+    match runner.run(
+        &strat_query_condition_simplify(),
+        |(schema, cells, condition): (Rc<SchemaData>, Rc<Cells>, QueryConditionExpr)| {
+            instance_query_condition_simplify(&schema, &cells, &condition)
+                .map_err(|e| TestCaseError::Fail(e.to_string().into()))
+        },
+    ) {
+        Ok(_) => Ok(true),
+        Err(e) => Err(anyhow!(e.to_string())),
+    }
+}
+
+/// Returns a [Strategy] which builds an element-addressed
+/// `tiledb_test_query_condition::Condition` against one of `cells`' own
+/// var-length byte fields (the only kind stored as [FieldData::VecUInt8]),
+/// sampling both the field/element position and the comparison value from a
+/// cell that actually has it, so the predicate has a real chance of
+/// selecting something. Yields `None` if `cells` has no such field, or every
+/// one of them is empty in every cell.
+fn strat_element_condition(cells: &Cells) -> impl Strategy<Value = Option<Condition>> {
+    let mut candidates = Vec::<(String, u32, u8)>::new();
+    for (field, data) in cells.fields().iter() {
+        let FieldData::VecUInt8(values) = data else {
+            continue;
+        };
+        for cell in values.iter() {
+            candidates.extend(
+                cell.iter()
+                    .enumerate()
+                    .map(|(index, byte)| (field.clone(), index as u32, *byte)),
+            );
+        }
+    }
+
+    if candidates.is_empty() {
+        return Just(None).boxed();
+    }
+
+    (proptest::sample::select(candidates), any::<bool>())
+        .prop_map(|((field, index, value), addr_any)| {
+            let builder = tiledb_test_query_condition::field(field);
+            let condition = if addr_any {
+                builder.any().eq(vec![value])
+            } else {
+                builder.element(index).eq(vec![value])
+            };
+            Some(Condition::from(condition))
+        })
+        .boxed()
+}
+
+/// Evaluates `condition` against `schema`/`cells` through the same
+/// `instance_query_condition_datafusion` FFI entry point used for a plain
+/// [QueryConditionExpr], via `tiledb_test_query_condition::ast_from_condition`
+/// instead of `ast_from_query_condition`. There's no independent oracle for
+/// the expected selection here (same as `instance_query_condition_datafusion`
+/// itself) -- this only exercises the element-addressed AST node against the
+/// evaluator without panicking or erroring.
+fn instance_query_condition_element(
+    schema: &SchemaData,
+    cells: &Cells,
+    condition: &Condition,
+) -> anyhow::Result<()> {
+    let cxx_schema = tiledb_test_array_schema::schema_from_pod(schema)?;
+    let cxx_tile = tiledb_test_result_tile::result_tile_from_cells(&cxx_schema, cells)?;
+
+    let cxx_ast = tiledb_test_query_condition::ast_from_condition(condition)?;
+    let _ = ffi::instance_query_condition_datafusion(&cxx_schema, &cxx_tile, &cxx_ast)?;
+
+    Ok(())
+}
+
+/// Returns a [Strategy] which produces inputs to `instance_query_condition_element`,
+/// reusing `strat_schema_and_cells` the same way `strat_query_condition_datafusion`
+/// does, then discarding cases whose cells have no field `strat_element_condition`
+/// can address.
+fn strat_query_condition_element()
+-> impl Strategy<Value = (Rc<SchemaData>, Rc<Cells>, Condition)> {
+    strat_schema_and_cells().prop_flat_map(|(schema, cells)| {
+        let cells = Rc::new(cells);
+        let schema_for_map = Rc::clone(&schema);
+        let cells_for_map = Rc::clone(&cells);
+        strat_element_condition(&cells).prop_filter_map(
+            "schema/cells must have an addressable field",
+            move |maybe_condition| {
+                maybe_condition
+                    .map(|condition| (Rc::clone(&schema_for_map), Rc::clone(&cells_for_map), condition))
+            },
+        )
+    })
+}
+
+/// Evaluates `instance_query_condition_element` against values drawn randomly
+/// from `strat_query_condition_element`.
+///
+/// Returns `Ok` if all test cases were successful and `Err` otherwise,
+/// logging the "minimum" failing example to standard output.
+fn proptest_query_condition_element() -> anyhow::Result<bool> {
+    let mut runner = TestRunner::new(proptest::test_runner::Config {
+        cases: 2048,
+        ..Default::default()
+    });
+    match runner.run(
+        &strat_query_condition_element(),
+        |(schema, cells, condition): (Rc<SchemaData>, Rc<Cells>, Condition)| {
+            instance_query_condition_element(&schema, &cells, &condition)
+                .map_err(|e| TestCaseError::Fail(e.to_string().into()))
+        },
+    ) {
+        Ok(_) => Ok(true),
+        Err(e) => Err(anyhow!(e.to_string())),
+    }
+}
@@ -2,8 +2,12 @@ pub mod enums;
 
 use itertools::Itertools;
 use tiledb_common::dimension_constraints_go;
-use tiledb_cxx_interface::sm::array_schema::{ArraySchema, Attribute, Dimension, Domain};
-use tiledb_pod::array::schema::{AttributeData, DimensionData, DomainData, SchemaData};
+use tiledb_cxx_interface::sm::array_schema::{
+    ArraySchema, Attribute, CellValNum, Dimension, Domain, Enumeration, new_enumeration,
+};
+use tiledb_pod::array::schema::{
+    AttributeData, DimensionData, DomainData, EnumerationData, SchemaData,
+};
 
 pub fn schema_from_pod(pod: &SchemaData) -> anyhow::Result<cxx::SharedPtr<ArraySchema>> {
     let domain = domain_from_pod(&pod.domain)?;
@@ -23,6 +27,17 @@ pub fn schema_from_pod(pod: &SchemaData) -> anyhow::Result<cxx::SharedPtr<ArrayS
 
         schema.as_mut().set_domain(domain)?;
 
+        // Enumerations must be attached to the schema before any attribute
+        // that binds to one by name is added, so that `add_attribute` below
+        // never races a lookup against an enumeration that isn't there yet.
+        pod.enumerations
+            .iter()
+            .flatten()
+            .map(enumeration_from_pod)
+            .process_results(|enums| {
+                enums.for_each(|enumeration| schema.as_mut().add_enumeration(enumeration))
+            })?;
+
         pod.attributes
             .iter()
             .map(attribute_from_pod)
@@ -52,7 +67,6 @@ pub fn schema_from_pod(pod: &SchemaData) -> anyhow::Result<cxx::SharedPtr<ArrayS
             schema.as_mut().set_allows_dups(allow_dups);
         }
 
-        // enumerations
         // coords filters
         // offsets filters
         // validity filters
@@ -63,6 +77,25 @@ pub fn schema_from_pod(pod: &SchemaData) -> anyhow::Result<cxx::SharedPtr<ArrayS
     ))
 }
 
+pub fn enumeration_from_pod(pod: &EnumerationData) -> anyhow::Result<cxx::SharedPtr<Enumeration>> {
+    cxx::let_cxx_string!(name = &pod.name);
+
+    let cell_val_num = pod
+        .cell_val_num
+        .map(enums::convert_cell_val_num)
+        .unwrap_or(CellValNum::Single);
+
+    let offsets = pod.offsets.as_deref().unwrap_or(&[]);
+
+    Ok(new_enumeration(
+        &name,
+        enums::convert_datatype(pod.datatype),
+        u32::from(cell_val_num),
+        &pod.data,
+        offsets,
+    ))
+}
+
 pub fn domain_from_pod(pod: &DomainData) -> anyhow::Result<cxx::SharedPtr<Domain>> {
     let mut d = tiledb_test_support::array_schema::new_domain(
         tiledb_test_support::get_test_memory_tracker(),
@@ -127,17 +160,23 @@ pub fn attribute_from_pod(pod: &AttributeData) -> anyhow::Result<cxx::SharedPtr<
     );
 
     {
-        let Some(attribute) = attribute.as_mut() else {
+        let Some(mut attribute) = attribute.as_mut() else {
             unreachable!()
         };
 
         if let Some(cvn) = pod.cell_val_num {
-            attribute.set_cell_val_num(u32::from(enums::convert_cell_val_num(cvn)));
+            attribute
+                .as_mut()
+                .set_cell_val_num(u32::from(enums::convert_cell_val_num(cvn)));
+        }
+
+        if let Some(enumeration_name) = &pod.enumeration {
+            cxx::let_cxx_string!(enumeration_name = enumeration_name);
+            attribute.as_mut().set_enumeration_name(&enumeration_name);
         }
 
         // fill value
         // filters
-        // enumeration
     }
 
     Ok(tiledb_test_support::array_schema::attribute_to_shared(
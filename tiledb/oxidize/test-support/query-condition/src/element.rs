@@ -0,0 +1,169 @@
+//! Element-addressed predicates over a list/fixed-size-list attribute, e.g.
+//! `field("f").element(2).eq(...)` to compare the 3rd value of a
+//! fixed-size cell, or `field("v").any().eq(...)` to match a var-length
+//! cell if any of its elements compares equal (an `array_contains`-style
+//! predicate, after DataFusion's `array_element`/`array_positions`).
+//!
+//! [QueryConditionExpr] itself has no notion of element addressing, so
+//! rather than extending it, [Condition] is a local superset that composes
+//! a [QueryConditionExpr] (via [Condition::Plain]) with an [ElementCondition]
+//! under the same `AND`/`OR`/`NOT` combinators, and [ast_from_condition]
+//! converts either down to an [ASTNode] the same way [crate::ast_from_query_condition]
+//! does.
+//!
+//! An out-of-range index, a null cell, or (for [ElementIndex::Any]) an empty
+//! var-length cell are all meant to produce a non-match rather than an
+//! error -- but deciding that is the evaluator's job; see
+//! [new_ast_element_value_node] for what's assumed to exist on the C++ side.
+//! `staticlibs/unit-query-condition` fuzzes [Condition] against that assumed
+//! evaluator via `strat_query_condition_element`/`proptest_query_condition_element`.
+
+use tiledb_common::query::condition::{CombinationOp, EqualityOp, QueryConditionExpr};
+use tiledb_cxx_interface::sm::query::ast::ASTNode;
+use tiledb_test_support_cxx_interface::query::new_ast_element_value_node;
+
+use crate::enums;
+
+/// Which element of a multi-valued cell an [ElementCondition] addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElementIndex {
+    /// The element at this position of a fixed-size cell.
+    At(u32),
+    /// Any element of a var-length cell (an `array_contains`-style match).
+    /// Never matches an empty cell.
+    Any,
+}
+
+impl ElementIndex {
+    fn to_cxx(self) -> u32 {
+        match self {
+            ElementIndex::At(i) => i,
+            // Reuses the same sentinel `CellValNum::Var` already uses for
+            // "variable" rather than inventing a second one.
+            ElementIndex::Any => u32::MAX,
+        }
+    }
+}
+
+/// A predicate comparing one element of a multi-valued attribute's cell
+/// against a literal value. Build one with [field].
+#[derive(Debug, Clone)]
+pub struct ElementCondition {
+    field: String,
+    index: ElementIndex,
+    op: EqualityOp,
+    value: Vec<u8>,
+}
+
+/// Starts building an [ElementCondition] against `field`, e.g.
+/// `field("f").element(2).eq(value_bytes)`.
+pub fn field(name: impl Into<String>) -> ElementFieldBuilder {
+    ElementFieldBuilder { field: name.into() }
+}
+
+pub struct ElementFieldBuilder {
+    field: String,
+}
+
+impl ElementFieldBuilder {
+    /// Addresses the element at fixed position `index`.
+    pub fn element(self, index: u32) -> ElementIndexBuilder {
+        ElementIndexBuilder {
+            field: self.field,
+            index: ElementIndex::At(index),
+        }
+    }
+
+    /// Addresses any element of a var-length cell.
+    pub fn any(self) -> ElementIndexBuilder {
+        ElementIndexBuilder {
+            field: self.field,
+            index: ElementIndex::Any,
+        }
+    }
+}
+
+pub struct ElementIndexBuilder {
+    field: String,
+    index: ElementIndex,
+}
+
+macro_rules! element_cmp {
+    ($name:ident, $op:expr) => {
+        pub fn $name(self, value: impl Into<Vec<u8>>) -> ElementCondition {
+            ElementCondition {
+                field: self.field,
+                index: self.index,
+                op: $op,
+                value: value.into(),
+            }
+        }
+    };
+}
+
+impl ElementIndexBuilder {
+    element_cmp!(eq, EqualityOp::Equal);
+    element_cmp!(ne, EqualityOp::NotEqual);
+    element_cmp!(lt, EqualityOp::Less);
+    element_cmp!(le, EqualityOp::LessEqual);
+    element_cmp!(ge, EqualityOp::GreaterEqual);
+    element_cmp!(gt, EqualityOp::Greater);
+}
+
+/// A [QueryConditionExpr] extended with [ElementCondition]s, so the two can
+/// be combined with the same `AND`/`OR`/`NOT` tree. Convert to an [ASTNode]
+/// with [ast_from_condition].
+#[derive(Debug, Clone)]
+pub enum Condition {
+    Plain(QueryConditionExpr),
+    Element(ElementCondition),
+    Comb {
+        lhs: Box<Condition>,
+        rhs: Box<Condition>,
+        op: CombinationOp,
+    },
+    Negate(Box<Condition>),
+}
+
+impl From<QueryConditionExpr> for Condition {
+    fn from(qc: QueryConditionExpr) -> Self {
+        Condition::Plain(qc)
+    }
+}
+
+impl From<ElementCondition> for Condition {
+    fn from(ec: ElementCondition) -> Self {
+        Condition::Element(ec)
+    }
+}
+
+/// Constructs an internal query condition syntax tree from a [Condition],
+/// the same way [crate::ast_from_query_condition] does for a bare
+/// [QueryConditionExpr].
+pub fn ast_from_condition(condition: &Condition) -> anyhow::Result<cxx::SharedPtr<ASTNode>> {
+    match condition {
+        Condition::Plain(qc) => crate::ast_from_query_condition(qc),
+        Condition::Element(ec) => ast_from_element_condition(ec),
+        Condition::Comb { lhs, rhs, op } => {
+            let lhs = ast_from_condition(lhs)?;
+            let rhs = ast_from_condition(rhs)?;
+            let op = enums::convert_combination_op(*op);
+            Ok(tiledb_test_support_cxx_interface::query::new_ast_combination(lhs, rhs, op))
+        }
+        Condition::Negate(c) => {
+            let arg = ast_from_condition(c)?;
+            Ok(tiledb_test_support_cxx_interface::query::new_ast_negate(arg))
+        }
+    }
+}
+
+fn ast_from_element_condition(ec: &ElementCondition) -> anyhow::Result<cxx::SharedPtr<ASTNode>> {
+    cxx::let_cxx_string! { field = &ec.field };
+    let op = enums::convert_equality_op(ec.op);
+    Ok(new_ast_element_value_node(
+        &field,
+        op,
+        &ec.value,
+        ec.index.to_cxx(),
+    ))
+}
@@ -4,7 +4,15 @@
 //! This enables property-based testing against arbitrary query conditions
 //! using the strategies we have already written in `tiledb_common`.
 
+mod element;
 mod enums;
+mod simplify;
+
+pub use element::{
+    Condition, ElementCondition, ElementFieldBuilder, ElementIndex, ElementIndexBuilder,
+    ast_from_condition, field,
+};
+pub use simplify::{Simplified, simplify};
 
 use tiledb_common::query::condition::*;
 use tiledb_cxx_interface::sm::query::ast::ASTNode;
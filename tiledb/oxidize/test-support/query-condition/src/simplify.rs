@@ -0,0 +1,183 @@
+//! A recursive simplification pass over [QueryConditionExpr], meant to run
+//! before [crate::ast_from_query_condition] so that a condition built up by
+//! folding together large `AND`/`OR` trees (as e.g. a range predicate like
+//! `4 <= d AND d <= 8` does) doesn't hand redundant or entirely-decided
+//! subtrees across the FFI boundary.
+//!
+//! TileDB's query conditions are not classically two-valued: a comparison
+//! against a null cell evaluates to `false` rather than to some third state
+//! that later resolves either way, and a negated predicate is evaluated
+//! against the null cell the same way rather than by complementing the
+//! non-null result. So for a nullable field, `x` and `NOT x` can both be
+//! `false` on the same cell, meaning `x OR NOT x` is not always `true`.
+//! [simplify] therefore only cancels `x` against `NOT x` when `x` is a
+//! [Predicate::Nullness] test (which never itself depends on the value a
+//! null cell is missing) or has already folded to a literal; it never does
+//! so for an arbitrary [Predicate::Equality] or [Predicate::SetMembership],
+//! since this module has no schema to check whether their field is
+//! nullable. Likewise, folding contradictory numeric ranges like `field >=
+//! lo AND field <= hi` with `lo > hi` would need an ordering over
+//! [tiledb_common]'s predicate values, which aren't exposed here beyond
+//! `to_bytes()` -- so that part of range folding is left undone.
+
+use tiledb_common::query::condition::{CombinationOp, Predicate, QueryConditionExpr};
+
+/// The result of [simplify]: either a (possibly rewritten) expression, or a
+/// literal outcome the original expression is now known to always produce,
+/// regardless of the cell it would have been evaluated against.
+#[derive(Debug, Clone)]
+pub enum Simplified {
+    Expr(QueryConditionExpr),
+    Literal(bool),
+}
+
+/// Recursively folds `expr`'s `AND`/`OR` tree: `And(x, FALSE) = FALSE`,
+/// `And(x, TRUE) = x`, `Or(x, TRUE) = TRUE`, `Or(x, FALSE) = x`, and
+/// idempotence `And(x, x) = x`/`Or(x, x) = x`. See the module docs for why
+/// `x`/`NOT x` cancellation and contradictory-range folding are only
+/// partially implemented.
+pub fn simplify(expr: QueryConditionExpr) -> Simplified {
+    match expr {
+        QueryConditionExpr::Cond(predicate) => Simplified::Expr(QueryConditionExpr::Cond(predicate)),
+        QueryConditionExpr::Negate(inner) => match simplify(*inner) {
+            Simplified::Literal(b) => Simplified::Literal(!b),
+            Simplified::Expr(inner) => Simplified::Expr(QueryConditionExpr::Negate(Box::new(inner))),
+        },
+        QueryConditionExpr::Comb { lhs, rhs, op } => combine(simplify(*lhs), simplify(*rhs), op),
+    }
+}
+
+fn combine(lhs: Simplified, rhs: Simplified, op: CombinationOp) -> Simplified {
+    use Simplified::{Expr, Literal};
+
+    match (lhs, rhs, op) {
+        (Literal(false), _, CombinationOp::And) | (_, Literal(false), CombinationOp::And) => {
+            Literal(false)
+        }
+        (Expr(x), Literal(true), CombinationOp::And) | (Literal(true), Expr(x), CombinationOp::And) => {
+            Expr(x)
+        }
+        (Literal(true), Literal(true), CombinationOp::And) => Literal(true),
+
+        (Literal(true), _, CombinationOp::Or) | (_, Literal(true), CombinationOp::Or) => Literal(true),
+        (Expr(x), Literal(false), CombinationOp::Or) | (Literal(false), Expr(x), CombinationOp::Or) => {
+            Expr(x)
+        }
+        (Literal(false), Literal(false), CombinationOp::Or) => Literal(false),
+
+        (Expr(l), Expr(r), op) => {
+            if exprs_equal(&l, &r) {
+                return Expr(l);
+            }
+            if let Some(outcome) = cancels(&l, &r, op) {
+                return Literal(outcome);
+            }
+            Expr(QueryConditionExpr::Comb {
+                lhs: Box::new(l),
+                rhs: Box::new(r),
+                op,
+            })
+        }
+    }
+}
+
+/// Detects `x AND NOT x` / `x OR NOT x`, returning the outcome it's safe to
+/// fold to -- `false` for `AND`, `true` for `OR` -- or `None` if `l`/`rhs`
+/// aren't such a pair, or `x` isn't [guaranteed_non_null].
+fn cancels(l: &QueryConditionExpr, r: &QueryConditionExpr, op: CombinationOp) -> Option<bool> {
+    let (negated, other) = match (l, r) {
+        (QueryConditionExpr::Negate(negated), other) => (negated.as_ref(), other),
+        (other, QueryConditionExpr::Negate(negated)) => (negated.as_ref(), other),
+        _ => return None,
+    };
+
+    if exprs_equal(negated, other) && guaranteed_non_null(other) {
+        Some(matches!(op, CombinationOp::Or))
+    } else {
+        None
+    }
+}
+
+/// An expression whose truth value is never itself suppressed to `false` by
+/// a null cell, so cancelling it against its own negation is sound.
+fn guaranteed_non_null(expr: &QueryConditionExpr) -> bool {
+    matches!(expr, QueryConditionExpr::Cond(Predicate::Nullness(_)))
+}
+
+fn exprs_equal(a: &QueryConditionExpr, b: &QueryConditionExpr) -> bool {
+    match (a, b) {
+        (QueryConditionExpr::Cond(pa), QueryConditionExpr::Cond(pb)) => predicates_equal(pa, pb),
+        (
+            QueryConditionExpr::Comb {
+                lhs: la,
+                rhs: ra,
+                op: oa,
+            },
+            QueryConditionExpr::Comb {
+                lhs: lb,
+                rhs: rb,
+                op: ob,
+            },
+        ) => same_variant(oa, ob) && exprs_equal(la, lb) && exprs_equal(ra, rb),
+        (QueryConditionExpr::Negate(a), QueryConditionExpr::Negate(b)) => exprs_equal(a, b),
+        _ => false,
+    }
+}
+
+fn predicates_equal(a: &Predicate, b: &Predicate) -> bool {
+    match (a, b) {
+        (Predicate::Equality(pa), Predicate::Equality(pb)) => {
+            pa.field() == pb.field()
+                && same_variant(&pa.operation(), &pb.operation())
+                && pa.value().to_bytes() == pb.value().to_bytes()
+        }
+        (Predicate::SetMembership(pa), Predicate::SetMembership(pb)) => {
+            pa.field() == pb.field()
+                && same_variant(&pa.operation(), &pb.operation())
+                && set_members_equal(pa.members(), pb.members())
+        }
+        (Predicate::Nullness(pa), Predicate::Nullness(pb)) => {
+            pa.field() == pb.field() && same_variant(&pa.operation(), &pb.operation())
+        }
+        _ => false,
+    }
+}
+
+fn set_members_equal(
+    a: &tiledb_common::query::condition::SetMembers,
+    b: &tiledb_common::query::condition::SetMembers,
+) -> bool {
+    use tiledb_common::query::condition::SetMembers;
+
+    match (a.as_ptr_and_size(), b.as_ptr_and_size()) {
+        (Some((ap, asz)), Some((bp, bsz))) => {
+            if asz != bsz {
+                return false;
+            }
+            if asz == 0 {
+                return true;
+            }
+            // SAFETY: `as_ptr_and_size` only returns `Some` with a valid
+            // pointer and the given size, the same assumption
+            // `ast_from_predicate` already relies on.
+            unsafe {
+                std::slice::from_raw_parts(ap as *const u8, asz as usize)
+                    == std::slice::from_raw_parts(bp as *const u8, bsz as usize)
+            }
+        }
+        (None, None) => {
+            let (SetMembers::String(sa), SetMembers::String(sb)) = (a, b) else {
+                // SAFETY: only way that `as_ptr_and_size()` is `None`
+                unreachable!()
+            };
+            sa == sb
+        }
+        _ => false,
+    }
+}
+
+/// Compares two values of the same fieldless-enum type by discriminant,
+/// without requiring `T: PartialEq`.
+fn same_variant<T>(a: &T, b: &T) -> bool {
+    std::mem::discriminant(a) == std::mem::discriminant(b)
+}
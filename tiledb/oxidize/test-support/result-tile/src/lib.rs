@@ -3,18 +3,34 @@
 //!
 //! This enables property-based testing against arbitrary tiles
 //! using the strategies we have already written in `tiledb_test_cells`.
+//!
+//! `Cells` itself has no notion of nullability, so functions which need to
+//! exercise nullable attributes accept validity information out-of-band via
+//! [NullMasks].
 
 use std::collections::HashMap;
+use std::io::{Read, Write};
 use std::ops::Deref;
 use std::sync::Arc;
 
-use arrow::array::{Array as ArrowArray, GenericListArray, PrimitiveArray};
-use arrow::buffer::OffsetBuffer;
-use arrow::datatypes::{Field as ArrowField, Schema as ArrowSchema};
+use arrow::array::{Array as ArrowArray, AsArray, GenericListArray, PrimitiveArray};
+use arrow::buffer::{NullBuffer, OffsetBuffer};
+use arrow::datatypes::{self as adt, Field as ArrowField, Schema as ArrowSchema};
+use arrow::ipc::reader::StreamReader;
+use arrow::ipc::writer::StreamWriter;
 use arrow::record_batch::RecordBatch;
 use tiledb_cxx_interface::sm::array_schema::{ArraySchema, CellValNum};
 use tiledb_cxx_interface::sm::query::readers::ResultTile;
-use tiledb_test_cells::{Cells, FieldData, typed_field_data_go};
+use tiledb_test_cells::{typed_field_data_go, Cells, FieldData};
+
+/// Per-field null masks keyed by field name, `true` meaning the cell is valid.
+///
+/// `tiledb_test_cells::Cells` has no representation of its own for nullable
+/// fields, so callers which want to exercise nullable attributes provide the
+/// validity bitmap for each such field here instead. A field absent from the
+/// map is treated as entirely valid (i.e. non-nullable as far as the
+/// generated `RecordBatch` is concerned).
+pub type NullMasks = HashMap<String, Vec<bool>>;
 
 /// Packages a `ResultTile` with the buffers which contain the tile data.
 pub struct PackagedResultTile {
@@ -25,8 +41,8 @@ pub struct PackagedResultTile {
     /// the `ResultTile` are byte units.
     #[allow(dead_code)]
     offsets: HashMap<Vec<u8>, Vec<u64>>,
-    /// Buffers for validity which is not bit-packed and thus not compatible with arrow.
-    /// Since `Cells` does not have nullable fields this can be shared by all fields.
+    /// Buffers for validity, one byte per cell, which is not bit-packed and
+    /// thus not compatible with arrow.
     #[allow(dead_code)]
     validity: HashMap<Vec<u8>, Vec<u8>>,
     // NB: tile data borrows the record batch columns, this is for sure "unsafe"
@@ -36,9 +52,80 @@ pub struct PackagedResultTile {
 
 impl PackagedResultTile {
     pub fn new(schema: &ArraySchema, batch: RecordBatch) -> anyhow::Result<PackagedResultTile> {
-        // FIXME: check that all nested fields are not nullable
         result_tile_from_record_batch(schema, batch)
     }
+
+    /// Serializes the backing [RecordBatch] to an Arrow IPC stream, tagging
+    /// it with a fingerprint of `schema` so that [PackagedResultTile::read_ipc]
+    /// can detect a fixture being replayed against a different schema than it
+    /// was captured with.
+    ///
+    /// The `offsets`/`validity` maps are not themselves serialized: they are
+    /// always re-derived deterministically from the batch and schema by
+    /// [PackagedResultTile::new], so persisting the batch is enough to
+    /// reproduce an identical [PackagedResultTile] on load.
+    ///
+    /// This lets a failing tile found while shrinking a proptest case be
+    /// checked into the repo as a regression fixture and replayed
+    /// deterministically, independent of the run's RNG seed.
+    pub fn write_ipc<W: Write>(&self, schema: &ArraySchema, writer: W) -> anyhow::Result<()> {
+        let ipc_schema = self
+            .buffers
+            .schema()
+            .as_ref()
+            .clone()
+            .with_metadata(HashMap::from([(
+                "tiledb.schema_fingerprint".to_owned(),
+                schema_fingerprint(schema)?,
+            )]));
+        let mut ipc_writer = StreamWriter::try_new(writer, &ipc_schema)?;
+        ipc_writer.write(&self.buffers)?;
+        ipc_writer.finish()?;
+        Ok(())
+    }
+
+    /// Loads a [PackagedResultTile] previously written by
+    /// [PackagedResultTile::write_ipc], bypassing `Cells` generation entirely.
+    pub fn read_ipc<R: Read>(
+        schema: &ArraySchema,
+        reader: R,
+    ) -> anyhow::Result<PackagedResultTile> {
+        let mut ipc_reader = StreamReader::try_new(reader, None)?;
+
+        let expected_fingerprint = schema_fingerprint(schema)?;
+        if let Some(fingerprint) = ipc_reader
+            .schema()
+            .metadata()
+            .get("tiledb.schema_fingerprint")
+        {
+            anyhow::ensure!(
+                *fingerprint == expected_fingerprint,
+                "IPC fixture was captured against a different schema than the one provided"
+            );
+        }
+
+        let batch = ipc_reader
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("IPC stream contains no record batches"))??;
+        anyhow::ensure!(
+            ipc_reader.next().is_none(),
+            "IPC stream contains more than one record batch"
+        );
+
+        PackagedResultTile::new(schema, batch)
+    }
+}
+
+/// A lightweight fingerprint of an [ArraySchema]'s field names and physical
+/// types, used to sanity-check that an IPC fixture is being replayed against
+/// the same logical schema it was captured with.
+fn schema_fingerprint(schema: &ArraySchema) -> anyhow::Result<String> {
+    let (arrow_schema, _) = tiledb_arrow::schema::to_arrow(
+        schema,
+        tiledb_arrow::ffi::WhichSchema::Storage,
+        &tiledb_arrow::schema::EnumerationTypeCache::default(),
+    )?;
+    Ok(format!("{arrow_schema:?}"))
 }
 
 impl Deref for PackagedResultTile {
@@ -53,10 +140,69 @@ pub fn result_tile_from_cells(
     schema: &ArraySchema,
     cells: &Cells,
 ) -> anyhow::Result<PackagedResultTile> {
-    let buffers = cells_to_record_batch(cells);
+    result_tile_from_cells_with_nulls(schema, cells, &NullMasks::new())
+}
+
+/// Constructs a [ResultTile] which contains the same logical contents of the
+/// requested `cells`, with the given `nulls` applied to produce nullable
+/// attributes. See [NullMasks] for how validity is specified.
+pub fn result_tile_from_cells_with_nulls(
+    schema: &ArraySchema,
+    cells: &Cells,
+    nulls: &NullMasks,
+) -> anyhow::Result<PackagedResultTile> {
+    let buffers = cells_to_record_batch(cells, nulls);
     PackagedResultTile::new(schema, buffers)
 }
 
+/// Constructs a [RecordBatch] with the same logical contents as `tile`,
+/// inverting [result_tile_from_record_batch]. This zero-copies out of the
+/// tile's buffers the same way [tiledb_arrow::record_batch::to_record_batch] does.
+///
+/// # Safety
+///
+/// See the safety docs of [tiledb_arrow::record_batch::to_record_batch]: the
+/// returned `RecordBatch` must not be used after `tile` is destructed.
+pub unsafe fn result_tile_to_record_batch(
+    schema: &ArraySchema,
+    tile: &ResultTile,
+) -> anyhow::Result<RecordBatch> {
+    let (arrow_schema, enumerations) = tiledb_arrow::schema::to_arrow(
+        schema,
+        tiledb_arrow::ffi::WhichSchema::Storage,
+        &tiledb_arrow::schema::EnumerationTypeCache::default(),
+    )?;
+    let arrow_schema = tiledb_arrow::schema::ArrowArraySchema {
+        schema: Arc::new(arrow_schema),
+        enumerations: Arc::new(enumerations),
+    };
+    let batch = unsafe {
+        // SAFETY: propagated up to our caller, see function docs
+        tiledb_arrow::record_batch::to_record_batch(&arrow_schema, tile)?
+    };
+    Ok(batch.arrow)
+}
+
+/// Reconstructs the logical `Cells` contents of `tile`, inverting
+/// [result_tile_from_cells]. This closes the loop for property tests, e.g.
+/// `cells == cells_from_result_tile(schema, &result_tile_from_cells(schema, &cells)?)?`,
+/// which catches offset-unit (byte vs element) and cell-val-num flattening
+/// bugs that the one-way conversion alone would silently tolerate.
+///
+/// # Safety
+///
+/// See [result_tile_to_record_batch].
+pub unsafe fn cells_from_result_tile(
+    schema: &ArraySchema,
+    tile: &ResultTile,
+) -> anyhow::Result<Cells> {
+    let batch = unsafe {
+        // SAFETY: propagated up to our caller, see function docs
+        result_tile_to_record_batch(schema, tile)?
+    };
+    Ok(record_batch_to_cells(&batch))
+}
+
 /// Constructs a [ResultTile] from an Arrow [RecordBatch].
 fn result_tile_from_record_batch(
     schema: &ArraySchema,
@@ -86,6 +232,13 @@ fn result_tile_from_record_batch(
             assert_eq!(1, column_data.buffers().len());
             assert_eq!(1, column_data.child_data().len());
             assert_eq!(1, column_data.child_data()[0].buffers().len());
+            // TileDB has no representation for nullability of the individual
+            // values within a variable-length cell, only for the cell itself.
+            assert!(
+                column_data.child_data()[0].nulls().is_none(),
+                "field {field_name:?} has independently nullable values, \
+                 which TileDB cannot represent"
+            );
 
             let value_width = column_data.child_data()[0]
                 .data_type()
@@ -100,6 +253,12 @@ fn result_tile_from_record_batch(
             // list type, whether FixedSizeListArray or ListArray is source data dependendent
             assert_eq!(1, column_data.child_data().len());
             assert_eq!(1, column_data.child_data()[0].buffers().len());
+            // as above, the fixed-size elements themselves cannot be independently nullable
+            assert!(
+                column_data.child_data()[0].nulls().is_none(),
+                "field {field_name:?} has independently nullable values, \
+                 which TileDB cannot represent"
+            );
 
             if column_data.buffers().len() == 1 {
                 // this came from a source such as `tiledb_test_cells::Cells` which does not
@@ -183,14 +342,18 @@ fn result_tile_from_record_batch(
     })
 }
 
-fn cells_to_record_batch(cells: &Cells) -> RecordBatch {
+fn cells_to_record_batch(cells: &Cells, nulls: &NullMasks) -> RecordBatch {
     let (fields, columns) = cells
         .fields()
         .iter()
         .map(|(fname, fdata)| {
-            let arrow_array = field_data_to_array(fdata);
+            let field_nulls = nulls
+                .get(fname.as_str())
+                .map(|mask| NullBuffer::from_iter(mask.iter().copied()));
+            let nullable = field_nulls.is_some();
+            let arrow_array = field_data_to_array(fdata, field_nulls);
             (
-                ArrowField::new(fname.to_owned(), arrow_array.data_type().clone(), false),
+                ArrowField::new(fname.to_owned(), arrow_array.data_type().clone(), nullable),
                 arrow_array,
             )
         })
@@ -207,12 +370,18 @@ fn cells_to_record_batch(cells: &Cells) -> RecordBatch {
     .unwrap()
 }
 
-fn field_data_to_array(field: &FieldData) -> Arc<dyn ArrowArray> {
+/// Converts `field` into an arrow array, optionally applying `nulls` to the
+/// outer (per-cell) validity. The values within a variable-length cell are
+/// never independently nullable, since TileDB has no representation for that.
+fn field_data_to_array(field: &FieldData, nulls: Option<NullBuffer>) -> Arc<dyn ArrowArray> {
     typed_field_data_go!(
         field,
         _DT,
         cells,
-        Arc::new(cells.iter().copied().collect::<PrimitiveArray<_>>()) as Arc<dyn ArrowArray>,
+        {
+            let values = cells.iter().copied().collect::<PrimitiveArray<_>>();
+            Arc::new(PrimitiveArray::new(values.values().clone(), nulls)) as Arc<dyn ArrowArray>
+        },
         {
             let values = cells
                 .iter()
@@ -227,9 +396,66 @@ fn field_data_to_array(field: &FieldData) -> Arc<dyn ArrowArray> {
                 )),
                 offsets,
                 Arc::new(values),
-                None,
+                nulls,
             );
             Arc::new(cells)
         }
     )
 }
+
+/// Inverts [cells_to_record_batch].
+fn record_batch_to_cells(batch: &RecordBatch) -> Cells {
+    let fields = batch
+        .schema()
+        .fields()
+        .iter()
+        .zip(batch.columns())
+        .map(|(field, column)| (field.name().clone(), array_to_field_data(column.as_ref())))
+        .collect::<HashMap<String, FieldData>>();
+    Cells::new(fields)
+}
+
+/// Inverts [field_data_to_array], ignoring the outer validity (if any):
+/// [Cells] has no representation for nullability, see [NullMasks].
+fn array_to_field_data(array: &dyn ArrowArray) -> FieldData {
+    macro_rules! primitive_field_data {
+        ($array:expr, $(($arrow_ty:ty, $variant:ident, $var_variant:ident)),+ $(,)?) => {
+            match $array.data_type() {
+                $(
+                    adt::DataType::$variant => FieldData::$variant(
+                        $array.as_primitive::<adt::$arrow_ty>().values().to_vec(),
+                    ),
+                )+
+                adt::DataType::LargeList(value_field) => match value_field.data_type() {
+                    $(
+                        adt::DataType::$variant => {
+                            let list = $array.as_list::<i64>();
+                            let values = list.values().as_primitive::<adt::$arrow_ty>();
+                            FieldData::$var_variant(
+                                (0..list.len())
+                                    .map(|i| values.values()[list.value_offsets()[i] as usize..list.value_offsets()[i + 1] as usize].to_vec())
+                                    .collect::<Vec<_>>(),
+                            )
+                        }
+                    )+
+                    other => unimplemented!("no `FieldData` variant for variable-length {other:?}"),
+                },
+                other => unimplemented!("no `FieldData` variant for {other:?}"),
+            }
+        };
+    }
+
+    primitive_field_data!(
+        array,
+        (Int8Type, Int8, VecInt8),
+        (Int16Type, Int16, VecInt16),
+        (Int32Type, Int32, VecInt32),
+        (Int64Type, Int64, VecInt64),
+        (UInt8Type, UInt8, VecUInt8),
+        (UInt16Type, UInt16, VecUInt16),
+        (UInt32Type, UInt32, VecUInt32),
+        (UInt64Type, UInt64, VecUInt64),
+        (Float32Type, Float32, VecFloat32),
+        (Float64Type, Float64, VecFloat64),
+    )
+}
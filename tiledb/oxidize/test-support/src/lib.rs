@@ -89,6 +89,17 @@ mod ffi {
             offsets: &[u64],
         ) -> SharedPtr<ASTNode>;
 
+        /// Builds an AST node which tests an element of a multi-valued cell
+        /// against `value`, rather than the whole cell: `element_index` of
+        /// `u32::MAX` means "any element" (a var-length `contains`-style
+        /// match), anything else addresses a single fixed position.
+        fn new_ast_element_value_node(
+            field: &CxxString,
+            op: QueryConditionOp,
+            value: &[u8],
+            element_index: u32,
+        ) -> SharedPtr<ASTNode>;
+
         fn new_ast_combination(
             left: SharedPtr<ASTNode>,
             right: SharedPtr<ASTNode>,
@@ -139,8 +150,8 @@ pub mod array_schema {
 
 pub mod query {
     pub use crate::ffi::{
-        new_ast_combination, new_ast_negate, new_ast_value_node, new_ast_value_node_null,
-        new_ast_value_node_var,
+        new_ast_combination, new_ast_element_value_node, new_ast_negate, new_ast_value_node,
+        new_ast_value_node_null, new_ast_value_node_var,
     };
 }
 